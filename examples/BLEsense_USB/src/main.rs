@@ -33,9 +33,10 @@ use arrayvec::ArrayString;
 use core::fmt;
 use core::fmt::Write;
 
-use lps2x::{interface::{I2cInterface,
+use lps2x::{interface::eh02::{I2cInterface,
                         i2c::I2cAddress}};
 use lps2x::*;
+use lps2x::variant::lps22hb::ODR;
 
 const BOOT_DELAY_MS: u16 = 100; //small delay for the I2C to initiate correctly and start on boot without having to reset the board
 
@@ -105,9 +106,9 @@ fn main() -> ! {
     let i2c_interface = I2cInterface::init(i2c1, I2cAddress::SA0_GND);
        
     // create a new driver instance with the I2C interface    
-    let mut lps2x = LPS2X::new(i2c_interface);
+    let lps2x = LPS2X::new(i2c_interface, LPS22HB);
 
-    lps2x.set_datarate(ODR::_1Hz).unwrap();
+    let mut lps2x = lps2x.into_continuous(ODR::_1Hz).map_err(|(_, error)| error).unwrap();
     
     loop {       
 
@@ -119,8 +120,8 @@ fn main() -> ! {
 
         let mut buf = ArrayString::<[u8; 32]>::new();
 
-        let temp = lps2x.read_temperature().unwrap();            
-        let press = lps2x.read_pressure().unwrap();
+        let temp = lps2x.read_temperature().unwrap().as_celsius();
+        let press = lps2x.read_pressure().unwrap().as_hpa();
         let id = lps2x.get_device_id().unwrap();
 
         format_reading(&mut buf, press, temp);
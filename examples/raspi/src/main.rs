@@ -3,7 +3,8 @@
 use rppal::i2c::I2c;
 
 use lps2x::*;
-use lps2x::interface::{I2cInterface,
+use lps2x::variant::lps25hb::ODR;
+use lps2x::interface::eh02::{I2cInterface,
     i2c::I2cAddress};
 
 fn main() {
@@ -14,19 +15,13 @@ fn main() {
     let i2c_interface = I2cInterface::init(i2c, I2cAddress::SA0_VCC); // Pololu board
 
     // create a new driver instance with the I2C interface    
-    let mut lps2x = LPS2X::new(i2c_interface);
-
-    // turn the sensor on 
-    lps2x.sensor_on(true).unwrap();
-      
-    // enable Block Data Update
-    lps2x.bdu_enable(true).unwrap();
-  
-    // set data rate to 7Hz
-    lps2x.set_datarate(ODR::_7Hz).unwrap();
-
-    let temp = lps2x.read_temperature().unwrap();            
-    let press = lps2x.read_pressure().unwrap();
+    let lps2x = LPS2X::new(i2c_interface, LPS25HB);
+
+    // turn the sensor on, enable Block Data Update and set data rate to 7Hz
+    let mut lps2x = lps2x.init::<Continuous>(config::SensorConfig::continuous(ODR::_7Hz)).map_err(|(_, error)| error).unwrap();
+
+    let temp = lps2x.read_temperature().unwrap().as_celsius();
+    let press = lps2x.read_pressure().unwrap().as_hpa();
     let id = lps2x.get_device_id().unwrap();
 
     println!("Device ID: {}\nPressure: {} hPa\nTemperature: {} °C", 
@@ -0,0 +1,140 @@
+//! Async version of the driver, based on the `embedded-hal-async` traits (requires the `async` feature)
+//!
+//! `LPS2XAsync` supports the same sensor models, power modes and settings as `LPS2X`,
+//! with `async` versions of its functions: both drivers are generated from the same code.
+//! The only difference in naming is `measure()`, the async version of `LPS2X::measure_blocking()`.
+//! A pin connected to the INT_DRDY line and implementing `Wait` can be added with `with_data_ready_pin()`,
+//! so that `read_when_ready()` sleeps until new data are available instead of polling the STATUS register.
+//! The data ready signal must be enabled on the sensor, see `InterruptConfig::enable_data_ready`.
+
+use super::*;
+use embedded_hal::digital::Error as _;
+use embedded_hal_async::digital::Wait;
+use interface::AsyncInterface;
+use mode::Powered;
+use sensor::Measurement;
+
+#[cfg(any(feature = "lps25hb", feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+pub use device::DeviceAsync;
+pub use fifo::FifoSamplesAsync;
+
+/// Placeholder for the data ready pin, when it is not used
+#[derive(Debug)]
+pub struct NoPin;
+
+/// Holds the async driver instance with the selected interface, sensor model, power mode and data ready pin
+pub struct LPS2XAsync<T, C, MODE = PowerDown, P = NoPin> {
+     pub(crate) interface: T,
+     /// Pressure sensitivity in LSB/hPa, updated by `init()` (and `full_scale()` on LPS28DFW)
+     pub(crate) press_sensitivity: u16,
+     /// Autozero function enabled (the pressure output is relative to REF_P), updated by `autozero_config()`
+     pub(crate) autozero: bool,
+     /// FIFO setup of the driver, updated by `configure_fifo()`, `arm_capture()`, `high_resolution_mode()`
+     /// and the output data rate changes
+     pub(crate) fifo_use: fifo::FifoUse,
+     drdy: P,
+     drdy_active: INT_ACTIVE,
+     variant: PhantomData<C>,
+     mode: PhantomData<MODE>,
+}
+
+impl<T, C, E> LPS2XAsync<T, C, PowerDown, NoPin>
+where
+     T: AsyncInterface<Error = E>,
+     C: Variant,
+{
+     /// Create a new instance of the driver for the given sensor model, e.g. `LPS2XAsync::new(interface, LPS25HB)`.
+     /// The sensor is expected to be in power-down mode (power-on default), use `init()`
+     /// or one of the `into_*()` functions to put it in a known mode.
+     pub fn new(interface: T, _variant: C) -> Self {
+          LPS2XAsync {
+               interface,
+               press_sensitivity: PRESS_SENSITIVITY,
+               autozero: false,
+               fifo_use: fifo::FifoUse::Other,
+               drdy: NoPin,
+               drdy_active: INT_ACTIVE::High,
+               variant: PhantomData,
+               mode: PhantomData,
+          }
+     }
+
+     /// Create a new instance of the driver for the given sensor model,
+     /// after checking the content of the WHO_AM_I register.
+     pub async fn new_checked(interface: T, variant: C) -> Result<Self, Error<E>> {
+          let mut sensor = Self::new(interface, variant);
+          let whoami = sensor.get_device_id().await?;
+          if whoami != C::WHOAMI {
+               return Err(Error::UnknownDevice(whoami));
+          }
+          Ok(sensor)
+     }
+}
+
+impl<T, C, MODE, P, E> LPS2XAsync<T, C, MODE, P>
+where
+     T: AsyncInterface<Error = E>,
+     C: Variant,
+     MODE: PowerMode,
+{
+     /// Add the pin connected to INT_DRDY, with the active level selected in `InterruptConfig`.
+     pub fn with_data_ready_pin<D: Wait>(self, pin: D, active: INT_ACTIVE) -> LPS2XAsync<T, C, MODE, D> {
+          LPS2XAsync {
+               interface: self.interface,
+               press_sensitivity: self.press_sensitivity,
+               autozero: self.autozero,
+               fifo_use: self.fifo_use,
+               drdy: pin,
+               drdy_active: active,
+               variant: PhantomData,
+               mode: PhantomData,
+          }
+     }
+
+     /// Destroy driver instance, return interface instance and data ready pin.
+     pub fn destroy(self) -> (T, P) {
+          (self.interface, self.drdy)
+     }
+
+     /// Change the type of the driver after the mode of the sensor has been changed.
+     pub(crate) fn into_mode<M: PowerMode>(self) -> LPS2XAsync<T, C, M, P> {
+          LPS2XAsync {
+               interface: self.interface,
+               press_sensitivity: self.press_sensitivity,
+               autozero: self.autozero,
+               fifo_use: self.fifo_use,
+               drdy: self.drdy,
+               drdy_active: self.drdy_active,
+               variant: PhantomData,
+               mode: PhantomData,
+          }
+     }
+}
+
+impl<T, C, MODE, P, E> LPS2XAsync<T, C, MODE, P>
+where
+     T: AsyncInterface<Error = E>,
+     C: Variant,
+     MODE: Powered,
+     P: Wait,
+{
+     /// Wait until the data ready pin is at its active level.
+     /// The pin is waited on its level, not on an edge: with the data ready signal in pulse mode
+     /// (DRDY_PLS on LPS22DF and LPS28DFW) a pulse can end before the pin is checked, use the default
+     /// mode, where the signal stays active until the output registers are read.
+     /// Returns `Error::Pin` with the kind of the pin error if the pin can't be read.
+     pub async fn wait_for_data_ready(&mut self) -> Result<(), Error<E>> {
+          match self.drdy_active {
+               INT_ACTIVE::High => self.drdy.wait_for_high().await,
+               INT_ACTIVE::Low => self.drdy.wait_for_low().await,
+          }
+          .map_err(|e| Error::Pin(e.kind()))
+     }
+
+     /// Wait until the data ready pin is active, then read pressure and temperature.
+     /// Returns `Error::Pin` if the pin can't be read, see `wait_for_data_ready()`.
+     pub async fn read_when_ready(&mut self) -> Result<Measurement, Error<E>> {
+          self.wait_for_data_ready().await?;
+          self.read_measurement().await
+     }
+}
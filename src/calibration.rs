@@ -0,0 +1,194 @@
+//! One-point calibration (OPC): pressure offset (RPDS) computed against a reference barometer
+
+use super::*;
+use convert::*;
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+use mode::Continuous;
+use units::Pressure;
+
+/// Result of a one-point calibration, see `calibrate_offset()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationReport {
+     /// Pressure given by the reference barometer
+     pub reference: Pressure,
+     /// Average pressure reading before the calibration
+     pub before: Pressure,
+     /// Average pressure reading after the calibration
+     pub after: Pressure,
+     /// Pressure offset (RPDS) before the calibration
+     pub previous_offset: Pressure,
+     /// Pressure offset (RPDS) written by the calibration
+     pub offset: Pressure,
+}
+
+impl CalibrationReport {
+     /// Error of the average pressure reading before the calibration (reading - reference)
+     pub fn error_before(&self) -> Pressure {
+          self.before - self.reference
+     }
+
+     /// Error of the average pressure reading after the calibration (reading - reference)
+     pub fn error_after(&self) -> Pressure {
+          self.after - self.reference
+     }
+}
+
+macro_rules! impl_calibration {
+     ($driver:ident $(<$pin:ident>)?, $interface:ident, $delay:ident; $($async:ident)?; $($await:ident)?) => {
+          impl<T, C, $($pin,)? E> $driver<T, C, Continuous $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+          {
+               /// Wait until a new pressure sample is available (P_DA), polling the STATUS register
+               /// every millisecond, and read it
+               $($async)? fn next_pressure_counts<D: $delay>(&mut self, delay: &mut D, timeout_ms: u16) -> Result<i32, Error<E>> {
+                    let mut elapsed: u16 = 0;
+                    while !self.is_register_bit_flag_high(C::STATUS, C::P_DA) $(.$await)? ? {
+                         if elapsed >= timeout_ms {
+                              return Err(Error::Timeout);
+                         }
+                         delay.delay_ms(1) $(.$await)?;
+                         elapsed += 1;
+                    }
+                    self.read_pressure_raw() $(.$await)?
+               }
+
+               /// Average of `samples` new pressure readings
+               $($async)? fn average_pressure<D: $delay>(&mut self, samples: u16, delay: &mut D, timeout_ms: u16) -> Result<Pressure, Error<E>> {
+                    // discard the sample in the output registers, it may be older than the last offset change
+                    self.read_pressure_raw() $(.$await)? ?;
+                    let mut sum: i64 = 0;
+                    for _ in 0..samples {
+                         sum += self.next_pressure_counts(delay, timeout_ms) $(.$await)? ? as i64;
+                    }
+                    Ok(pressure_from_counts((sum / samples as i64) as i32, self.press_sensitivity))
+               }
+
+               /// One-point calibration: average `samples` pressure readings, compare them with the `reference`
+               /// pressure given by a reference barometer, write the resulting signed offset in RPDS
+               /// (the sensor subtracts RPDS from the measured pressure), read it back, and average
+               /// `samples` readings again to check the remaining error.
+               /// The sensor must be in continuous mode with autozero disabled.
+               /// Returns `Error::InvalidConfig` if `samples` is 0 or autozero is enabled,
+               /// `Error::OutOfRange` if the offset doesn't fit in the RPDS registers,
+               /// `Error::Mismatch` if RPDS doesn't read back the written value,
+               /// `Error::Timeout` if a sample is not available after `timeout_ms` milliseconds.
+               pub $($async)? fn calibrate_offset<D: $delay>(
+                    &mut self,
+                    reference: Pressure,
+                    samples: u16,
+                    delay: &mut D,
+                    timeout_ms: u16,
+               ) -> Result<CalibrationReport, Error<E>> {
+                    if samples == 0 || self.autozero {
+                         return Err(Error::InvalidConfig);
+                    }
+                    let previous_offset = self.read_pressure_offset() $(.$await)? ?;
+                    let before = self.average_pressure(samples, delay, timeout_ms) $(.$await)? ?;
+                    let offset = previous_offset + (before - reference);
+                    let payload = pressure_offset_to_bytes(offset, self.press_sensitivity).ok_or(Error::OutOfRange)?;
+                    self.write_register(C::RPDS_L, payload[0]) $(.$await)? ?;
+                    self.write_register(C::RPDS_H, payload[1]) $(.$await)? ?;
+
+                    let mut data = [0u8; 2];
+                    self.read_registers(C::RPDS_L, &mut data) $(.$await)? ?;
+                    if data != payload {
+                         return Err(Error::Mismatch);
+                    }
+
+                    let after = self.average_pressure(samples, delay, timeout_ms) $(.$await)? ?;
+                    Ok(CalibrationReport {
+                         reference,
+                         before,
+                         after,
+                         previous_offset,
+                         offset: pressure_offset_from_bytes(payload, self.press_sensitivity),
+                    })
+               }
+          }
+     };
+}
+
+impl_calibration!(LPS2X, Interface, DelayNs; ;);
+#[cfg(feature = "async")]
+impl_calibration!(LPS2XAsync<P>, AsyncInterface, AsyncDelayNs; async; await);
+
+#[cfg(all(test, feature = "lps25hb"))]
+mod tests {
+     use super::*;
+     use mock::{NoDelay, RegisterFile};
+
+     /// Sensor reading 1002 hPa for the first `before` pressure reads, then 1000 hPa
+     fn sensor(before: usize) -> LPS2X<RegisterFile, LPS25HB, Continuous> {
+          let mut registers = RegisterFile::new();
+          registers.registers[<LPS25HB as Variant>::STATUS as usize] = LPS25HB::P_DA;
+          registers.registers[LPS25HB::PRESS_OUT_XL as usize..][..3].copy_from_slice(&[0x00, 0x80, 0x3E]);
+          registers.script(LPS25HB::PRESS_OUT_XL + 1, &[0xA0; 3][..before]);
+          registers.driver(LPS25HB)
+     }
+
+     #[test]
+     fn calibrate_offset_writes_and_verifies_the_offset() {
+          // discarded sample and 2 readings before, the same after
+          let mut sensor = sensor(3);
+          let report = sensor.calibrate_offset(Pressure::from_hectopascal(1000), 2, &mut NoDelay::default(), 10).unwrap();
+
+          assert_eq!(report.previous_offset, Pressure::from_hectopascal(0));
+          assert_eq!(report.before, Pressure::from_hectopascal(1002));
+          assert_eq!(report.offset, Pressure::from_hectopascal(2));
+          assert_eq!(report.after, Pressure::from_hectopascal(1000));
+          assert_eq!(report.error_before(), Pressure::from_hectopascal(2));
+          assert_eq!(report.error_after(), Pressure::from_hectopascal(0));
+          // 2 hPa x 16
+          assert_eq!(sensor.interface.written(LPS25HB::RPDS_L), [0x20]);
+          assert_eq!(sensor.interface.written(LPS25HB::RPDS_H), [0x00]);
+     }
+
+     #[test]
+     fn calibrate_offset_adds_the_error_to_the_previous_offset() {
+          let mut sensor = sensor(3);
+          // -1 hPa
+          sensor.interface.registers[LPS25HB::RPDS_L as usize..][..2].copy_from_slice(&[0xF0, 0xFF]);
+          let report = sensor.calibrate_offset(Pressure::from_hectopascal(1000), 2, &mut NoDelay::default(), 10).unwrap();
+
+          assert_eq!(report.previous_offset, Pressure::from_hectopascal(-1));
+          assert_eq!(report.offset, Pressure::from_hectopascal(1));
+          assert_eq!(sensor.interface.written(LPS25HB::RPDS_L), [0x10]);
+     }
+
+     #[test]
+     fn calibrate_offset_reports_an_offset_not_read_back() {
+          let mut sensor = sensor(3);
+          // previous offset, then the read back
+          sensor.interface.script(LPS25HB::RPDS_L, &[0x00, 0x00]);
+          let result = sensor.calibrate_offset(Pressure::from_hectopascal(1000), 2, &mut NoDelay::default(), 10);
+          assert!(matches!(result, Err(Error::Mismatch)));
+     }
+
+     #[test]
+     fn calibrate_offset_rejects_invalid_settings() {
+          let mut sensor = sensor(0);
+          let result = sensor.calibrate_offset(Pressure::from_hectopascal(1000), 0, &mut NoDelay::default(), 10);
+          assert!(matches!(result, Err(Error::InvalidConfig)));
+
+          sensor.autozero = true;
+          let result = sensor.calibrate_offset(Pressure::from_hectopascal(1000), 2, &mut NoDelay::default(), 10);
+          assert!(matches!(result, Err(Error::InvalidConfig)));
+          assert!(sensor.interface.writes.is_empty());
+     }
+
+     #[test]
+     fn calibrate_offset_times_out_without_new_samples() {
+          let mut sensor = sensor(0);
+          sensor.interface.registers[<LPS25HB as Variant>::STATUS as usize] = 0;
+          let mut delay = NoDelay::default();
+          let result = sensor.calibrate_offset(Pressure::from_hectopascal(1000), 2, &mut delay, 10);
+
+          assert!(matches!(result, Err(Error::Timeout)));
+          assert_eq!(delay.elapsed_ns, 10_000_000);
+          assert!(sensor.interface.writes.is_empty());
+     }
+}
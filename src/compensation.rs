@@ -0,0 +1,356 @@
+//! Temperature-dependent pressure offset compensation
+//!
+//! The RPDS register applies a constant offset only. [`TempCompensation`] models the offset
+//! of the pressure readings as a polynomial of the temperature (up to degree 3), fitted from
+//! a calibration sweep against a reference barometer, and subtracts it from the readings.
+//!
+//! The model is evaluated with integer arithmetic only; fitting it requires the `float` feature.
+//! It can be saved and loaded as a compact blob of [`BLOB_LEN`] bytes, e.g. in flash memory.
+//!
+//! ```
+//! use lps2x::compensation::TempCompensation;
+//! use lps2x::units::{Pressure, Temperature};
+//!
+//! // offset of 10 centipascal/°C, 0 at 20 °C (the coefficients have 16 fractional bits)
+//! let compensation = TempCompensation::from_coefficients(1, Temperature::from_centidegrees(2000), [0, 10 << 16, 0, 0]).unwrap();
+//! assert_eq!(compensation.offset(Temperature::from_centidegrees(4000)), Pressure::from_centipascal(200));
+//!
+//! let blob = compensation.to_bytes();
+//! assert_eq!(TempCompensation::from_bytes(&blob), Some(compensation));
+//! ```
+//!
+//! Fitting the model from a calibration sweep (`float` feature):
+//!
+//! ```
+//! # #[cfg(feature = "float")] {
+//! use lps2x::compensation::{CompensationPoint, TempCompensation};
+//! use lps2x::units::{Pressure, Temperature};
+//!
+//! // offsets measured at -20, 20 and 60 °C
+//! let points = [
+//!     CompensationPoint { temperature: Temperature::from_centidegrees(-2000), offset: Pressure::from_pascal(-4) },
+//!     CompensationPoint { temperature: Temperature::from_centidegrees(2000), offset: Pressure::from_pascal(0) },
+//!     CompensationPoint { temperature: Temperature::from_centidegrees(6000), offset: Pressure::from_pascal(4) },
+//! ];
+//! let compensation = TempCompensation::fit(&points, 1).unwrap();
+//! let offset = compensation.offset(Temperature::from_centidegrees(4000));
+//! assert!((offset.as_centipascal() - 200).abs() <= 1);
+//! # }
+//! ```
+
+use crate::sensor::Measurement;
+use crate::units::{Pressure, Temperature};
+
+/// Maximum degree of the polynomial
+pub const MAX_DEGREE: usize = 3;
+
+/// Length of the blob holding a compensation model, see `TempCompensation::to_bytes()`
+pub const BLOB_LEN: usize = 20;
+
+/// Version of the blob format, in the upper 4 bits of the first byte
+const BLOB_VERSION: u8 = 1;
+
+/// The coefficients are fixed-point numbers with 16 fractional bits
+const COEFF_SHIFT: u32 = 16;
+
+/// Offset between the pressure reading and the reference pressure at a given temperature
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompensationPoint {
+     /// Temperature of the sensor
+     pub temperature: Temperature,
+     /// Pressure reading - reference pressure
+     pub offset: Pressure,
+}
+
+impl CompensationPoint {
+     /// Calibration point from a measurement and the pressure given by a reference barometer at the same time
+     pub fn from_measurement(measurement: &Measurement, reference: Pressure) -> Self {
+          CompensationPoint {
+               temperature: measurement.temperature,
+               offset: measurement.pressure - reference,
+          }
+     }
+}
+
+/// Pressure offset as a polynomial of the temperature:
+/// offset(T) = c0 + c1 (T - T0) + c2 (T - T0)^2 + c3 (T - T0)^3, with T and T0 in degrees Celsius
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TempCompensation {
+     /// Degree of the polynomial (0 to `MAX_DEGREE`)
+     degree: u8,
+     /// Reference temperature T0, in hundredths of a degree Celsius
+     reference: i16,
+     /// Coefficients in hundredths of a pascal per degree Celsius to the power k, with 16 fractional bits
+     coefficients: [i32; MAX_DEGREE + 1],
+}
+
+impl TempCompensation {
+     /// Model from its coefficients, see `coefficients()`.
+     /// Returns `None` if the degree is greater than `MAX_DEGREE`.
+     pub fn from_coefficients(degree: u8, reference: Temperature, coefficients: [i32; MAX_DEGREE + 1]) -> Option<Self> {
+          if degree as usize > MAX_DEGREE {
+               return None;
+          }
+          let mut model = TempCompensation {
+               degree,
+               reference: reference.as_centidegrees() as i16,
+               coefficients,
+          };
+          // the coefficients above the degree are not used
+          for coefficient in model.coefficients.iter_mut().skip(degree as usize + 1) {
+               *coefficient = 0;
+          }
+          Some(model)
+     }
+
+     /// Degree of the polynomial
+     pub fn degree(&self) -> u8 {
+          self.degree
+     }
+
+     /// Reference temperature T0 of the polynomial
+     pub fn reference(&self) -> Temperature {
+          Temperature::from_centidegrees(self.reference as i32)
+     }
+
+     /// Coefficients c0..c3, in hundredths of a pascal per degree Celsius to the power k, as fixed-point numbers
+     /// with 16 fractional bits (e.g. 65536 is 1 centipascal/°C for c1)
+     pub fn coefficients(&self) -> [i32; MAX_DEGREE + 1] {
+          self.coefficients
+     }
+
+     /// Pressure offset at the given temperature
+     pub fn offset(&self, temperature: Temperature) -> Pressure {
+          let delta = (temperature.as_centidegrees() - self.reference as i32) as i64;
+          // Horner's method, the temperature difference is in hundredths of a degree
+          let mut value: i64 = 0;
+          for &coefficient in self.coefficients[..=self.degree as usize].iter().rev() {
+               value = value * delta / 100 + coefficient as i64;
+          }
+          Pressure::from_centipascal((value / (1 << COEFF_SHIFT)) as i32)
+     }
+
+     /// Pressure reading compensated for the offset at the given temperature
+     pub fn compensate(&self, pressure: Pressure, temperature: Temperature) -> Pressure {
+          pressure - self.offset(temperature)
+     }
+
+     /// Measurement with the pressure compensated for the offset at the measured temperature
+     pub fn compensate_measurement(&self, measurement: Measurement) -> Measurement {
+          Measurement {
+               pressure: self.compensate(measurement.pressure, measurement.temperature),
+               ..measurement
+          }
+     }
+
+     /// Save the model as a blob: format version and degree, reference temperature, coefficients
+     /// (least significant byte first) and checksum
+     pub fn to_bytes(&self) -> [u8; BLOB_LEN] {
+          let mut blob = [0u8; BLOB_LEN];
+          blob[0] = BLOB_VERSION << 4 | self.degree;
+          blob[1..3].copy_from_slice(&self.reference.to_le_bytes());
+          for (chunk, coefficient) in blob[3..BLOB_LEN - 1].chunks_exact_mut(4).zip(self.coefficients.iter()) {
+               chunk.copy_from_slice(&coefficient.to_le_bytes());
+          }
+          // the sum of all the bytes is 0
+          blob[BLOB_LEN - 1] = blob[..BLOB_LEN - 1].iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)).wrapping_neg();
+          blob
+     }
+
+     /// Load a model saved with `to_bytes()`.
+     /// Returns `None` if the format version, the degree or the checksum is wrong.
+     pub fn from_bytes(blob: &[u8; BLOB_LEN]) -> Option<Self> {
+          if blob.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 || blob[0] >> 4 != BLOB_VERSION {
+               return None;
+          }
+          let mut coefficients = [0i32; MAX_DEGREE + 1];
+          for (coefficient, chunk) in coefficients.iter_mut().zip(blob[3..BLOB_LEN - 1].chunks_exact(4)) {
+               *coefficient = i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+          }
+          let reference = i16::from_le_bytes([blob[1], blob[2]]);
+          Self::from_coefficients(blob[0] & 0x0F, Temperature::from_centidegrees(reference as i32), coefficients)
+     }
+}
+
+#[cfg(feature = "float")]
+impl TempCompensation {
+     /// Least-squares fit of a polynomial of the given degree to the calibration points,
+     /// with the reference temperature T0 at their average temperature.
+     /// Returns `None` if the degree is greater than `MAX_DEGREE`, there are not more points than the degree,
+     /// the temperatures don't allow the fit (e.g. all the same) or a coefficient is out of range.
+     pub fn fit(points: &[CompensationPoint], degree: u8) -> Option<Self> {
+          let n = degree as usize + 1;
+          if n > MAX_DEGREE + 1 || points.len() < n {
+               return None;
+          }
+          let sum: i64 = points.iter().map(|point| point.temperature.as_centidegrees() as i64).sum();
+          let reference = (sum / points.len() as i64) as i16;
+
+          // normal equations: matrix[i][j] = sum(x^(i+j)), matrix[i][n] = sum(y x^i)
+          let mut matrix = [[0f64; MAX_DEGREE + 2]; MAX_DEGREE + 1];
+          for point in points {
+               let x = (point.temperature.as_centidegrees() - reference as i32) as f64 / 100.0;
+               let y = point.offset.as_centipascal() as f64;
+               let mut powers = [1f64; 2 * MAX_DEGREE + 1];
+               for k in 1..powers.len() {
+                    powers[k] = powers[k - 1] * x;
+               }
+               for i in 0..n {
+                    for j in 0..n {
+                         matrix[i][j] += powers[i + j];
+                    }
+                    matrix[i][n] += y * powers[i];
+               }
+          }
+
+          // Gaussian elimination with partial pivoting
+          for column in 0..n {
+               let pivot = (column..n).fold(column, |best, row| {
+                    if abs(matrix[row][column]) > abs(matrix[best][column]) { row } else { best }
+               });
+               if abs(matrix[pivot][column]) < 1e-9 {
+                    return None;
+               }
+               matrix.swap(column, pivot);
+               let pivot_row = matrix[column];
+               for row in matrix[column + 1..n].iter_mut() {
+                    let factor = row[column] / pivot_row[column];
+                    for (value, pivot_value) in row[column..=n].iter_mut().zip(pivot_row[column..=n].iter()) {
+                         *value -= factor * pivot_value;
+                    }
+               }
+          }
+          let mut solution = [0f64; MAX_DEGREE + 1];
+          for row in (0..n).rev() {
+               let known: f64 = (row + 1..n).map(|k| matrix[row][k] * solution[k]).sum();
+               solution[row] = (matrix[row][n] - known) / matrix[row][row];
+          }
+
+          let mut coefficients = [0i32; MAX_DEGREE + 1];
+          for (coefficient, value) in coefficients.iter_mut().zip(solution.iter()).take(n) {
+               let fixed = value * (1u32 << COEFF_SHIFT) as f64;
+               if !(i32::MIN as f64..=i32::MAX as f64).contains(&fixed) {
+                    return None;
+               }
+               *coefficient = round(fixed);
+          }
+          Self::from_coefficients(degree, Temperature::from_centidegrees(reference as i32), coefficients)
+     }
+}
+
+/// Absolute value (no `f64::abs()` in `core` on older toolchains)
+#[cfg(feature = "float")]
+fn abs(value: f64) -> f64 {
+     if value < 0.0 { -value } else { value }
+}
+
+/// Round to the nearest integer, halfway cases away from zero
+#[cfg(feature = "float")]
+fn round(value: f64) -> i32 {
+     if value < 0.0 {
+          (value - 0.5) as i32
+     } else {
+          (value + 0.5) as i32
+     }
+}
+
+#[cfg(test)]
+mod tests {
+     use super::*;
+
+     #[cfg(feature = "float")]
+     fn point(centidegrees: i32, offset_centipascal: i32) -> CompensationPoint {
+          CompensationPoint {
+               temperature: Temperature::from_centidegrees(centidegrees),
+               offset: Pressure::from_centipascal(offset_centipascal),
+          }
+     }
+
+     #[test]
+     fn offset_is_evaluated_around_the_reference_temperature() {
+          // 1 Pa + 5 centipascal/°C + 0.5 centipascal/°C^2
+          let compensation = TempCompensation::from_coefficients(2, Temperature::from_centidegrees(2000), [100 << 16, 5 << 16, 1 << 15, 0]).unwrap();
+          assert_eq!(compensation.offset(Temperature::from_centidegrees(2000)), Pressure::from_centipascal(100));
+          assert_eq!(compensation.offset(Temperature::from_centidegrees(3000)), Pressure::from_centipascal(100 + 50 + 50));
+          // 100 - 100 + 200
+          assert_eq!(compensation.offset(Temperature::from_centidegrees(0)), Pressure::from_centipascal(200));
+          assert_eq!(
+               compensation.compensate(Pressure::from_hectopascal(1000), Temperature::from_centidegrees(3000)),
+               Pressure::from_centipascal(1000 * 10_000 - 200)
+          );
+     }
+
+     #[test]
+     fn coefficients_above_the_degree_are_ignored() {
+          let compensation = TempCompensation::from_coefficients(1, Temperature::from_centidegrees(0), [1, 2, 3, 4]).unwrap();
+          assert_eq!(compensation.coefficients(), [1, 2, 0, 0]);
+          assert_eq!(TempCompensation::from_coefficients(4, Temperature::from_centidegrees(0), [0; 4]), None);
+     }
+
+     #[test]
+     fn blob_round_trip() {
+          let compensation =
+               TempCompensation::from_coefficients(3, Temperature::from_centidegrees(-1234), [-123_456, 7_890_123, -42, i32::MIN]).unwrap();
+          let blob = compensation.to_bytes();
+          assert_eq!(blob[0], BLOB_VERSION << 4 | 3);
+          assert_eq!(blob.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)), 0);
+          assert_eq!(TempCompensation::from_bytes(&blob), Some(compensation));
+     }
+
+     #[test]
+     fn blob_with_a_wrong_checksum_version_or_degree_is_rejected() {
+          let blob = TempCompensation::from_coefficients(1, Temperature::from_centidegrees(2500), [100, 200, 0, 0]).unwrap().to_bytes();
+          for index in 0..BLOB_LEN {
+               let mut corrupted = blob;
+               corrupted[index] ^= 0x01;
+               assert_eq!(TempCompensation::from_bytes(&corrupted), None);
+          }
+          // valid checksum, but version 2
+          let mut corrupted = blob;
+          corrupted[0] += 0x10;
+          corrupted[BLOB_LEN - 1] -= 0x10;
+          assert_eq!(TempCompensation::from_bytes(&corrupted), None);
+          // valid checksum, but degree 4
+          let mut corrupted = blob;
+          corrupted[0] += 3;
+          corrupted[BLOB_LEN - 1] -= 3;
+          assert_eq!(TempCompensation::from_bytes(&corrupted), None);
+     }
+
+     #[cfg(feature = "float")]
+     #[test]
+     fn fit_recovers_a_polynomial() {
+          // offset = 300 + 4 (T - 20) - 0.25 (T - 20)^2 centipascal, with T in °C
+          let offset = |centidegrees: i32| {
+               let x = (centidegrees - 2000) as f64 / 100.0;
+               round(300.0 + 4.0 * x - 0.25 * x * x)
+          };
+          let points = [-2000, 0, 2000, 4000, 6000].map(|t| point(t, offset(t)));
+          let compensation = TempCompensation::fit(&points, 2).unwrap();
+
+          assert_eq!(compensation.degree(), 2);
+          assert_eq!(compensation.reference(), Temperature::from_centidegrees(2000));
+          let [c0, c1, c2, c3] = compensation.coefficients();
+          assert!((c0 - (300 << 16)).abs() <= 1);
+          assert!((c1 - (4 << 16)).abs() <= 1);
+          assert!((c2 + (1 << 14)).abs() <= 1);
+          assert_eq!(c3, 0);
+          for t in [-1000, 1000, 3000, 5000] {
+               assert!((compensation.offset(Temperature::from_centidegrees(t)).as_centipascal() - offset(t)).abs() <= 1);
+          }
+     }
+
+     #[cfg(feature = "float")]
+     #[test]
+     fn fit_rejects_unusable_points() {
+          let points = [point(2000, 0), point(2000, 10), point(2000, 20)];
+          // too few points for the degree
+          assert_eq!(TempCompensation::fit(&points[..2], 2), None);
+          assert_eq!(TempCompensation::fit(&points, 4), None);
+          // all at the same temperature
+          assert_eq!(TempCompensation::fit(&points, 1), None);
+          // the average of the offsets for degree 0
+          let compensation = TempCompensation::fit(&points, 0).unwrap();
+          assert_eq!(compensation.offset(Temperature::from_centidegrees(5000)), Pressure::from_centipascal(10));
+     }
+}
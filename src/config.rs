@@ -1,174 +1,596 @@
 //! Various functions related to configuration
 
 use super::*;
- 
-
-impl<T, E> LPS2X<T>
-where
-     T: Interface<Error = E>,
-     {
-     /// Set output data rate        
-     pub fn set_datarate(&mut self, odr: ODR) -> Result<(), T::Error> {
-          let mut reg_data = [0u8];
-          self.interface
-               .read(Registers::CTRL_REG1.addr(), &mut reg_data)?;
-          let mut payload = reg_data[0];
-          payload &= !Bitmasks::ODR_MASK;
-          payload |= odr.value();
-          self.interface.write(Registers::CTRL_REG1.addr(), payload)?;
-          Ok(())
-     }
+use fifo::FIFOConfig;
+use interrupt::InterruptConfig;
+use mode::{Continuous, PowerDown};
+#[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+use variant::lps22hb;
+#[cfg(any(feature = "lps22df", feature = "lps28dfw"))]
+use variant::lps22df::{self, AVG};
+#[cfg(feature = "lps22hh")]
+use variant::lps22hh;
+#[cfg(feature = "lps25hb")]
+use variant::lps25hb::{self, PRESS_RES, TEMP_RES};
+#[cfg(feature = "lps28dfw")]
+use variant::lps28dfw::FS_MODE;
 
-     #[cfg(feature = "lps25hb")]
-    /// Temperature internal average configuration (default 64).
-          pub fn temperature_resolution(&mut self, resolution: TEMP_RES) -> Result<(), T::Error> {
-          let mut reg_data = [0u8];
-          self.interface
-               .read(Registers::RES_CONF.addr(), &mut reg_data)?;
-          let mut payload = reg_data[0];
-          payload &= !Bitmasks::AVGT_MASK;
-          payload |= resolution.value();
-          self.interface.write(Registers::RES_CONF.addr(), payload)?;
-          Ok(())
-     }
+/// Sensor settings, applied all together by `init()`
+#[derive(Debug)]
+pub struct SensorConfig<C: Variant> {
+    /// Output data rate (OneShot: power-down / one-shot mode)
+    pub odr: C::ODR, // default OneShot
+    /// Enable block data update
+    pub enable_bdu: FLAG, // default enabled
+    #[cfg(feature = "lps25hb")]
+    /// Turn the sensor on (LPS25HB only, must be enabled in one-shot and continuous modes)
+    pub power_on: FLAG, // default enabled
+    #[cfg(feature = "lps25hb")]
+    /// Temperature internal average (LPS25HB only)
+    pub temperature_resolution: TEMP_RES, // default 64
+    #[cfg(feature = "lps25hb")]
+    /// Pressure internal average (LPS25HB only)
+    pub pressure_resolution: PRESS_RES, // default 512
+    #[cfg(any(feature = "lps22df", feature = "lps28dfw"))]
+    /// Pressure and temperature internal average (LPS22DF and LPS28DFW only)
+    pub averaging: AVG, // default 4
+    #[cfg(feature = "lps28dfw")]
+    /// Full-scale mode, selects the pressure sensitivity (LPS28DFW only)
+    pub full_scale: FS_MODE, // default 1260 hPa
+    #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+    /// Enable low-pass filter on pressure data in Continuous mode (all models except LPS25HB)
+    pub enable_lowpass: FLAG, // default disabled
+    #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+    /// Low-pass filter bandwidth: ODR/9 if disabled, ODR/20 if enabled
+    /// (ODR/4 and ODR/9 on LPS22DF and LPS28DFW; all models except LPS25HB)
+    pub lowpass_config: FLAG, // default disabled
+    #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+    /// Enable low current mode (LPS22HB, LPS33HW and LPS35HW only)
+    pub enable_low_current: FLAG, // default disabled
+    #[cfg(feature = "lps22hh")]
+    /// Enable low noise mode, with output data rates lower than 100 Hz (LPS22HH only)
+    pub enable_low_noise: FLAG, // default disabled
+}
 
-     #[cfg(feature = "lps25hb")]
-     /// Pressure internal average configuration (default 512).
-     pub fn pressure_resolution(&mut self, resolution: PRESS_RES) -> Result<(), T::Error> {
-          let mut reg_data = [0u8];
-          self.interface
-               .read(Registers::RES_CONF.addr(), &mut reg_data)?;
-          let mut payload = reg_data[0];
-          payload &= !Bitmasks::AVGP_MASK;
-          payload |= resolution.value();
-          self.interface.write(Registers::RES_CONF.addr(), payload)?;
-          Ok(())
-     }
+impl<C: Variant> Default for SensorConfig<C> {
+    fn default() -> Self {
+        SensorConfig {
+            odr: C::ODR::default(),                   // power-down / one-shot
+            enable_bdu: FLAG::Enabled,                // enabled
+            #[cfg(feature = "lps25hb")]
+            power_on: FLAG::Enabled,                  // enabled
+            #[cfg(feature = "lps25hb")]
+            temperature_resolution: TEMP_RES::_64,    // 64 (register default)
+            #[cfg(feature = "lps25hb")]
+            pressure_resolution: PRESS_RES::_512,     // 512 (register default)
+            #[cfg(any(feature = "lps22df", feature = "lps28dfw"))]
+            averaging: AVG::_4,                       // 4 (register default)
+            #[cfg(feature = "lps28dfw")]
+            full_scale: FS_MODE::_1260hPa,            // 1260 hPa (register default)
+            #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+            enable_lowpass: FLAG::Disabled,           // disabled
+            #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+            lowpass_config: FLAG::Disabled,           // ODR/9 (ODR/4 on LPS22DF and LPS28DFW)
+            #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+            enable_low_current: FLAG::Disabled,       // disabled
+            #[cfg(feature = "lps22hh")]
+            enable_low_noise: FLAG::Disabled,         // disabled (low current)
+        }
+    }
+}
+
+/// Complete device configuration, as returned by `read_config()`
+#[derive(Debug)]
+pub struct DeviceConfig<C: Variant> {
+    /// Sensor settings (CTRL_REG1, RES_CONF)
+    pub sensor: SensorConfig<C>,
+    /// FIFO enabled (CTRL_REG2)
+    pub fifo_on: FIFO_ON,
+    /// FIFO settings (CTRL_REG2, FIFO_CTRL)
+    pub fifo: FIFOConfig<C>,
+    /// Interrupt settings (CTRL_REG1..4, INTERRUPT_CFG, depending on the model)
+    pub interrupts: InterruptConfig,
+}
+
+#[cfg(feature = "lps22hh")]
+impl SensorConfig<LPS22HH> {
+    /// Preset: continuous mode with low noise mode enabled (output data rate lower than 100 Hz)
+    pub fn low_noise(odr: lps22hh::ODR) -> Self {
+        SensorConfig {
+            odr,
+            enable_low_noise: FLAG::Enabled,
+            ..Self::default()
+        }
+    }
+}
 
-     /// Enable or disable block data update
-     pub fn bdu_enable(&mut self, flag: bool) -> Result<(), T::Error> {
-          match flag {
-               true => self.set_register_bit_flag(Registers::CTRL_REG1, Bitmasks::BDU),
-               false => self.clear_register_bit_flag(Registers::CTRL_REG1, Bitmasks::BDU),
+#[cfg(feature = "lps28dfw")]
+impl SensorConfig<LPS28DFW> {
+    /// Preset: continuous mode with the given output data rate in 4060 hPa full-scale mode
+    pub fn full_scale_4060hpa(odr: lps22df::ODR) -> Self {
+        SensorConfig {
+            odr,
+            full_scale: FS_MODE::_4060hPa,
+            ..Self::default()
+        }
+    }
+}
+
+/// Raw content of the configuration registers read by `read_config()`
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigRegisters {
+    pub ctrl_reg1: u8,
+    pub ctrl_reg2: u8,
+    pub ctrl_reg3: u8,
+    /// CTRL_REG4, 0 if the model doesn't have it
+    pub ctrl_reg4: u8,
+    pub res_conf: u8,
+    pub fifo_ctrl: u8,
+    /// FIFO_WTM, 0 if the model doesn't have it
+    pub fifo_wtm: u8,
+    pub interrupt_cfg: u8,
+}
+
+impl<C: Variant> SensorConfig<C> {
+    /// Preset: power-down mode (sensor turned off on LPS25HB), with block data update
+    pub fn power_down() -> Self {
+        SensorConfig {
+            #[cfg(feature = "lps25hb")]
+            power_on: FLAG::Disabled,
+            ..Self::default()
+        }
+    }
+
+    /// Preset: one-shot mode, with block data update
+    pub fn one_shot() -> Self {
+        Self::default()
+    }
+
+    /// Preset: continuous mode with the given output data rate, with block data update
+    pub fn continuous(odr: C::ODR) -> Self {
+        SensorConfig {
+            odr,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(feature = "lps25hb")]
+impl SensorConfig<LPS25HB> {
+    /// Preset: one-shot mode with the lowest number of internal averages, for the lowest current consumption
+    pub fn low_power() -> Self {
+        SensorConfig {
+            temperature_resolution: TEMP_RES::_8,
+            pressure_resolution: PRESS_RES::_8,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+impl<C: Variant<ODR = lps22hb::ODR>> SensorConfig<C> {
+    /// Preset: one-shot mode with low current mode enabled (LPS22HB, LPS33HW and LPS35HW)
+    pub fn low_power() -> Self {
+        SensorConfig {
+            enable_low_current: FLAG::Enabled,
+            ..Self::default()
+        }
+    }
+}
+
+macro_rules! impl_config {
+     ($driver:ident $(<$pin:ident>)?, $interface:ident; $($async:ident)?; $($await:ident)?) => {
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               MODE: PowerMode,
+               {
+               /// Write CTRL_REG1 and RES_CONF with the settings of `config`, checking that they match the mode `M`
+               $($async)? fn write_sensor_config<M: PowerMode>(&mut self, config: &SensorConfig<C>) -> Result<(), Error<E>> {
+                    let (ctrl_reg1, res_conf) = C::config_registers(config).ok_or(Error::InvalidConfig)?;
+                    if !mode::mode_matches::<C, M>(ctrl_reg1) {
+                         return Err(Error::InvalidConfig);
+                    }
+                    // RES_CONF must be changed in power-down mode
+                    self.fifo_use = self.fifo_use.odr_changed();
+                    self.write_register(C::CTRL_REG1, ctrl_reg1 & !C::ODR_MASK) $(.$await)? ?;
+                    let res_conf = match C::RES_CONF_MASK {
+                         0xFF => res_conf,
+                         mask => (self.read_register(C::RES_CONF) $(.$await)? ? & !mask) | res_conf,
+                    };
+                    self.write_register(C::RES_CONF, res_conf) $(.$await)? ?;
+                    if ctrl_reg1 & C::ODR_MASK != 0 {
+                         self.write_register(C::CTRL_REG1, ctrl_reg1) $(.$await)? ?;
+                    }
+                    self.press_sensitivity = C::press_sensitivity(config);
+                    Ok(())
+               }
+
+               /// Apply a complete sensor configuration, writing CTRL_REG1 and RES_CONF,
+               /// and enter the power mode selected by the type parameter, e.g. `init::<Continuous>(config)`.
+               /// Settings not covered by `SensorConfig` and held in these registers (e.g. differential
+               /// interrupts on LPS25HB) are set to their default value.
+               /// Returns the driver with `Error::InvalidConfig` if the settings can't be used together,
+               /// or don't match the power mode (e.g. `ODR::OneShot` in continuous mode),
+               /// or with the bus error if the registers can't be written.
+               pub $($async)? fn init<M: PowerMode>(mut self, config: SensorConfig<C>) -> Result<$driver<T, C, M $(, $pin)?>, (Self, Error<E>)> {
+                    match self.write_sensor_config::<M>(&config) $(.$await)? {
+                         Ok(()) => Ok(self.into_mode()),
+                         Err(error) => Err((self, error)),
+                    }
+               }
+
+               /// Read back the configuration registers (CTRL_REG1..4, RES_CONF, FIFO_CTRL, INTERRUPT_CFG)
+               /// and decode them into the same settings used to configure the device.
+               /// The pressure sensitivity of the driver is updated from the settings found (LPS28DFW full-scale mode),
+               /// e.g. for a driver created with `new()` on a sensor configured beforehand.
+               /// Returns `Error::InvalidConfig` if a register holds a reserved value.
+               pub $($async)? fn read_config(&mut self) -> Result<DeviceConfig<C>, Error<E>> {
+                    let registers = ConfigRegisters {
+                         ctrl_reg1: self.read_register(C::CTRL_REG1) $(.$await)? ?,
+                         ctrl_reg2: self.read_register(C::CTRL_REG2) $(.$await)? ?,
+                         ctrl_reg3: self.read_register(C::CTRL_REG3) $(.$await)? ?,
+                         ctrl_reg4: match C::CTRL_REG4 {
+                              Some(address) => self.read_register(address) $(.$await)? ?,
+                              None => 0,
+                         },
+                         res_conf: self.read_register(C::RES_CONF) $(.$await)? ?,
+                         fifo_ctrl: self.read_register(C::FIFO_CTRL) $(.$await)? ?,
+                         fifo_wtm: match C::FIFO_WTM {
+                              Some(address) => self.read_register(address) $(.$await)? ?,
+                              None => 0,
+                         },
+                         interrupt_cfg: self.read_register(C::INTERRUPT_CFG) $(.$await)? ?,
+                    };
+                    let config = C::decode_config(&registers).ok_or(Error::InvalidConfig)?;
+                    // the pressure readings follow the full-scale mode found (LPS28DFW)
+                    self.press_sensitivity = C::press_sensitivity(&config.sensor);
+                    Ok(config)
+               }
+
+               /// Enable or disable block data update
+               pub $($async)? fn bdu_enable(&mut self, flag: bool) -> Result<(), Error<E>> {
+                    match flag {
+                         true => self.set_register_bit_flag(C::BDU_REG, C::BDU) $(.$await)?,
+                         false => self.clear_register_bit_flag(C::BDU_REG, C::BDU) $(.$await)?,
+                    }
+               }
+
+               /// AUTOZERO: when set to ‘1’, the measured pressure is used
+               /// as the reference in REF_P (the actual pressure output value is copied into REF_P).
+               /// From that point on the output pressure registers are updated and the same value
+               /// is also used for interrupt generation.
+               /// The register content of REF_P is subtracted from the measured pressure.
+               /// PRESS_OUT = measured pressure - REF_P
+               /// P_DIFF_IN = measured pressure - REF_P
+               /// The driver keeps track of the setting, see `read_absolute_pressure()` and `read_differential_pressure()`.
+               pub $($async)? fn autozero_config(&mut self, flag: bool) -> Result<(), Error<E>> {
+                   match flag {
+                         true => self.set_register_bit_flag(C::AUTOZERO_REG, C::AUTOZERO) $(.$await)?,
+                         false => self.clear_register_bit_flag(C::AUTOZERO_REG, C::AUTOZERO) $(.$await)?,
+                    }?;
+                    self.autozero = flag;
+                    Ok(())
+               }
+
+               /// Is the autozero function enabled? (as set by `autozero_config()`, the driver assumes
+               /// the power-on default, disabled, when it is created)
+               pub fn autozero_enabled(&self) -> bool {
+                    self.autozero
+               }
+
+               /// Resets the Autozero function. Self-cleared.
+               pub $($async)? fn autozero_reset(&mut self) -> Result<(), Error<E>> {
+                    self.set_register_bit_flag(C::RESET_AZ_REG, C::RESET_AZ) $(.$await)?
+               }
+
+               /// Disables I2C interface (default 0, I2C enabled)
+               pub $($async)? fn i2c_disable(&mut self, flag: bool) -> Result<(), Error<E>> {
+                    match flag {
+                    true => self.set_register_bit_flag(C::I2C_DIS_REG, C::I2C_DIS) $(.$await)?,
+                    false => self.clear_register_bit_flag(C::I2C_DIS_REG, C::I2C_DIS) $(.$await)?,
+                    }
+               }
+
+               /// Reboot. Refreshes the content of the internal registers stored in the Flash memory block.
+               /// At device power-up the content of the Flash memory block is transferred to the internal registers
+               /// related to the trimming functions to allow correct behavior of the device itself.
+               /// If for any reason the content of the trimming registers is modified,
+               /// it is sufficient to use this bit to restore the correct values.
+               /// At the end of the boot process the BOOT bit is set again to ‘0’ by hardware.
+               /// The BOOT bit takes effect after one ODR clock cycle.
+               /// Waits until the BOOT bit is cleared, returns `Error::Timeout` if it never clears.
+               pub $($async)? fn reboot(&mut self) -> Result<(), Error<E>> {
+                    self.set_register_bit_flag(C::CTRL_REG2, C::BOOT) $(.$await)? ?;
+                    self.wait_for_bit_flag_low(C::CTRL_REG2, C::BOOT) $(.$await)?
+               }
+
+               /// Set the SWRESET bit and wait until it is cleared
+               $($async)? fn reset(&mut self) -> Result<(), Error<E>> {
+                   self.set_register_bit_flag(C::CTRL_REG2, C::SWRESET) $(.$await)? ?;
+                   self.wait_for_bit_flag_low(C::CTRL_REG2, C::SWRESET) $(.$await)? ?;
+                   // power-on configuration: autozero disabled, LPS28DFW back in 1260 hPa full-scale mode, FIFO in Bypass mode
+                   self.autozero = false;
+                   self.press_sensitivity = C::press_sensitivity(&SensorConfig::default());
+                   self.fifo_use = fifo::FifoUse::Other;
+                   Ok(())
+               }
+
+               /// Run software reset (resets the device to the power-on configuration, takes 4 usec)
+               /// Waits until the SWRESET bit is cleared, returns the driver with `Error::Timeout` if it never clears
+               /// (or with the bus error). The device is in power-down mode after the reset.
+               pub $($async)? fn software_reset(mut self) -> Result<$driver<T, C, PowerDown $(, $pin)?>, (Self, Error<E>)> {
+                   match self.reset() $(.$await)? {
+                         Ok(()) => Ok(self.into_mode()),
+                         Err(error) => Err((self, error)),
+                   }
+               }
           }
-     }
- 
-     /// AUTOZERO: when set to ‘1’, the measured pressure is used
-     /// as the reference in REF_P (the actual pressure output value is copied into REF_P).
-     /// From that point on the output pressure registers are updated and the same value
-     /// is also used for interrupt generation.
-     /// The register content of REF_P is subtracted from the measured pressure.
-     /// PRESS_OUT = measured pressure - REF_P
-     /// P_DIFF_IN = measured pressure - REF_P
-     
-     #[cfg(feature = "lps22hb")]
-     pub fn autozero_config(&mut self, flag: bool) -> Result<(), T::Error> {
-         match flag {          
-               true => self.set_register_bit_flag(Registers::INTERRUPT_CFG, Bitmasks::AUTOZERO),          
-               false => self.clear_register_bit_flag(Registers::INTERRUPT_CFG, Bitmasks::AUTOZERO),         
+
+          impl<T, C, $($pin,)? E> $driver<T, C, Continuous $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               {
+               /// Set output data rate (use `into_one_shot()` or `into_power_down()` to stop continuous mode,
+               /// returns `Error::InvalidConfig` if the output data rate is `ODR::OneShot`)
+               pub $($async)? fn set_datarate(&mut self, odr: C::ODR) -> Result<(), Error<E>> {
+                    let odr = C::odr_value(odr);
+                    if odr == 0 {
+                         return Err(Error::InvalidConfig);
+                    }
+                    let mut payload = self.read_register(C::CTRL_REG1) $(.$await)? ?;
+                    payload &= !C::ODR_MASK;
+                    payload |= odr;
+                    self.fifo_use = self.fifo_use.odr_changed();
+                    self.write_register(C::CTRL_REG1, payload) $(.$await)? ?;
+                    Ok(())
+               }
           }
-     }
-     
-     #[cfg(feature = "lps25hb")]
-     pub fn autozero_config(&mut self, flag: bool) -> Result<(), T::Error> {
-          match flag {                    
-               true => self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::AUTOZERO),          
-               false => self.clear_register_bit_flag(Registers::CTRL_REG2, Bitmasks::AUTOZERO),
+
+          #[cfg(feature = "lps25hb")]
+          impl<T, MODE, $($pin,)? E> $driver<T, LPS25HB, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               MODE: PowerMode,
+               {
+              /// Temperature internal average configuration (default 64).
+               pub $($async)? fn temperature_resolution(&mut self, resolution: TEMP_RES) -> Result<(), Error<E>> {
+                    let mut payload = self.read_register(lps25hb::Registers::RES_CONF.addr()) $(.$await)? ?;
+                    payload &= !lps25hb::Bitmasks::AVGT_MASK;
+                    payload |= resolution.value();
+                    self.write_register(lps25hb::Registers::RES_CONF.addr(), payload) $(.$await)? ?;
+                    Ok(())
+               }
+
+               /// Pressure internal average configuration (default 512).
+               pub $($async)? fn pressure_resolution(&mut self, resolution: PRESS_RES) -> Result<(), Error<E>> {
+                    let mut payload = self.read_register(lps25hb::Registers::RES_CONF.addr()) $(.$await)? ?;
+                    payload &= !lps25hb::Bitmasks::AVGP_MASK;
+                    payload |= resolution.value();
+                    self.write_register(lps25hb::Registers::RES_CONF.addr(), payload) $(.$await)? ?;
+                    Ok(())
+               }
           }
-     }
- 
-     #[cfg(lps22hb)]
-     /// Resets the Autozero function. Self-cleared.
-     pub fn autozero_reset(&mut self) -> Result<(), T::Error> {          
-          self.set_register_bit_flag(Registers::INTERRUPT_CFG, Bitmasks::RESET_AZ)
-     }
-     
-     #[cfg(lps25hb)]
-     /// Resets the Autozero function. Self-cleared.
-     pub fn autozero_reset(&mut self) -> Result<(), T::Error> {     
-          self.set_register_bit_flag(Registers::CTRL_REG1, Bitmasks::RESET_AZ)      
-     }
- 
-     /// Disables I2C interface (default 0, I2C enabled)
-     pub fn i2c_disable(&mut self, flag: bool) -> Result<(), T::Error> {
-          match flag {
-          true => self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::I2C_DIS),
-          false => self.clear_register_bit_flag(Registers::CTRL_REG2, Bitmasks::I2C_DIS),
+
+          #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant<ODR = lps22hb::ODR>,
+               MODE: PowerMode,
+               {
+               /// Register address automatically incremented during a multiple byte access with a serial interface (I2C or SPI).
+               /// Default value: enabled
+               pub $($async)? fn address_incrementing(&mut self, flag: bool) -> Result<(), Error<E>> {
+                    match flag {
+                         true => self.set_register_bit_flag(lps22hb::Registers::CTRL_REG2.addr(), lps22hb::Bitmasks::IF_ADD_INC) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hb::Registers::CTRL_REG2.addr(), lps22hb::Bitmasks::IF_ADD_INC) $(.$await)?,
+                    }
+               }
+
+               /// Is reboot phase running?
+               pub $($async)? fn reboot_running(&mut self) -> Result<bool, Error<E>> {
+                  self.is_register_bit_flag_high(lps22hb::Registers::INT_SOURCE.addr(), lps22hb::Bitmasks::BOOT_STATUS) $(.$await)?
+               }
+
+               /// Enable and configure low-pass filter on pressure data in Continuous mode
+               pub $($async)? fn lowpass_filter(&mut self, enable: bool, configure: bool) -> Result<(), Error<E>> {
+                    match enable {
+                         true => self.set_register_bit_flag(lps22hb::Registers::CTRL_REG1.addr(), lps22hb::Bitmasks::EN_LPFP) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hb::Registers::CTRL_REG1.addr(), lps22hb::Bitmasks::EN_LPFP) $(.$await)?,
+                    }?;
+                    match configure {
+                         true => self.set_register_bit_flag(lps22hb::Registers::CTRL_REG1.addr(), lps22hb::Bitmasks::LPFP_CFG) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hb::Registers::CTRL_REG1.addr(), lps22hb::Bitmasks::LPFP_CFG) $(.$await)?,
+                    }?;
+                    Ok(())
+               }
+
+               /// Reset low-pass filter.  If the LPFP is active, in order to avoid the transitory phase,
+               /// the filter can be reset by reading this register before generating pressure measurements.
+               pub $($async)? fn lowpass_filter_reset(&mut self) -> Result<(), Error<E>> {
+                    self.read_register(lps22hb::Registers::LPFP_RES.addr()) $(.$await)? ?;
+                    Ok(())
+               }
+
+               /// AUTORIFP: when set to ‘1’, the measured pressure is copied into REF_P and used as the reference
+               /// for interrupt generation only. Unlike autozero, the output pressure registers keep the absolute pressure.
+               /// P_DIFF_IN = measured pressure - REF_P
+               /// PRESS_OUT = measured pressure
+               pub $($async)? fn autorifp_config(&mut self, flag: bool) -> Result<(), Error<E>> {
+                    match flag {
+                         true => self.set_register_bit_flag(lps22hb::Registers::INTERRUPT_CFG.addr(), lps22hb::Bitmasks::AUTORIFP) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hb::Registers::INTERRUPT_CFG.addr(), lps22hb::Bitmasks::AUTORIFP) $(.$await)?,
+                    }
+               }
+
+               /// Resets the AUTORIFP function. Self-cleared.
+               pub $($async)? fn autorifp_reset(&mut self) -> Result<(), Error<E>> {
+                    self.set_register_bit_flag(lps22hb::Registers::INTERRUPT_CFG.addr(), lps22hb::Bitmasks::RESET_ARP) $(.$await)?
+               }
           }
-     }
- 
-     #[cfg(feature = "lps22hb")]
-     /// Register address automatically incremented during a multiple byte access with a serial interface (I2C or SPI).
-     /// Default value: enabled
-     // THIS COULD BE USED AS A REPLACEMENT OF THE MULTIBYTE FOR LPS25HB 
-     // AND INCLUDED IN AN INITIAL CONFIGURATION OF THE SENSOR     
-     pub fn address_incrementing(&mut self, flag: bool) -> Result<(), T::Error> {
-          match flag {
-               true => self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::IF_ADD_INC),
-               false => self.clear_register_bit_flag(Registers::CTRL_REG2, Bitmasks::IF_ADD_INC),
+
+          #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+          impl<T, C, $($pin,)? E> $driver<T, C, PowerDown $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant<ODR = lps22hb::ODR>,
+               {
+               /// Enable low-power mode (low current mode can be changed in power-down mode only)
+               pub $($async)? fn enable_low_power(&mut self) -> Result<(), Error<E>> {
+                    self.set_register_bit_flag(lps22hb::Registers::RES_CONF.addr(), lps22hb::Bitmasks::LC_EN) $(.$await)?
+               }
           }
-     }
 
-     #[cfg(feature = "lps25hb")]
-     /// Turn the sensor on (sensor is in power down by default)
-     pub fn sensor_on(&mut self, flag: bool) -> Result<(), T::Error> {
-          match flag {
-               true => self.set_register_bit_flag(Registers::CTRL_REG1, Bitmasks::PD),
-               false => self.clear_register_bit_flag(Registers::CTRL_REG1, Bitmasks::PD),
+          #[cfg(feature = "lps22hh")]
+          impl<T, MODE, $($pin,)? E> $driver<T, LPS22HH, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               MODE: PowerMode,
+               {
+               /// Register address automatically incremented during a multiple byte access with a serial interface (I2C or SPI).
+               /// Default value: enabled
+               pub $($async)? fn address_incrementing(&mut self, flag: bool) -> Result<(), Error<E>> {
+                    match flag {
+                         true => self.set_register_bit_flag(lps22hh::Registers::CTRL_REG2.addr(), lps22hh::Bitmasks::IF_ADD_INC) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hh::Registers::CTRL_REG2.addr(), lps22hh::Bitmasks::IF_ADD_INC) $(.$await)?,
+                    }
+               }
+
+               /// Is reboot phase running?
+               pub $($async)? fn reboot_running(&mut self) -> Result<bool, Error<E>> {
+                  self.is_register_bit_flag_high(lps22hh::Registers::INT_SOURCE.addr(), lps22hh::Bitmasks::BOOT_ON) $(.$await)?
+               }
+
+               /// Enable and configure low-pass filter on pressure data in Continuous mode
+               pub $($async)? fn lowpass_filter(&mut self, enable: bool, configure: bool) -> Result<(), Error<E>> {
+                    match enable {
+                         true => self.set_register_bit_flag(lps22hh::Registers::CTRL_REG1.addr(), lps22hh::Bitmasks::EN_LPFP) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hh::Registers::CTRL_REG1.addr(), lps22hh::Bitmasks::EN_LPFP) $(.$await)?,
+                    }?;
+                    match configure {
+                         true => self.set_register_bit_flag(lps22hh::Registers::CTRL_REG1.addr(), lps22hh::Bitmasks::LPFP_CFG) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hh::Registers::CTRL_REG1.addr(), lps22hh::Bitmasks::LPFP_CFG) $(.$await)?,
+                    }?;
+                    Ok(())
+               }
           }
-     }
- 
-          /// Reboot. Refreshes the content of the internal registers stored in the Flash memory block.
-     /// At device power-up the content of the Flash memory block is transferred to the internal registers
-     /// related to the trimming functions to allow correct behavior of the device itself.
-     /// If for any reason the content of the trimming registers is modified,
-     /// it is sufficient to use this bit to restore the correct values.
-     /// At the end of the boot process the BOOT bit is set again to ‘0’ by hardware.
-     /// The BOOT bit takes effect after one ODR clock cycle.
-     pub fn reboot(&mut self) -> Result<(), T::Error> {
-          self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::BOOT)
-     }
-     
-     #[cfg(feature = "lps22hb")]
-     /// Is reboot phase running?
-     // IS THERE A SIMILAR BIT IN LPS25HB THAT COULD BE USED?
-     pub fn reboot_running(&mut self) -> Result<bool, T::Error> {
-        self.is_register_bit_flag_high(Registers::INT_SOURCE, Bitmasks::BOOT_STATUS)
-     }
 
-     /// Run software reset (resets the device to the power-on configuration, takes 4 usec)
-     pub fn software_reset(&mut self) -> Result<(), T::Error> {
-         self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::SWRESET)
-     }
-    
-     #[cfg(feature = "lps22hb")]
-     /// Enable low-power mode (must be done only with the device in power-down mode)
-     // SWITCHING INTO POWER-DOWN COULD BE ADDED TO THIS FUNCTION
-     pub fn enable_low_power(&mut self) -> Result<(), T::Error> {
-          self.set_register_bit_flag(Registers::RES_CONF, Bitmasks::LC_EN)
+          #[cfg(feature = "lps22hh")]
+          impl<T, $($pin,)? E> $driver<T, LPS22HH, PowerDown $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               {
+               /// Enable or disable low noise mode (can be changed in power-down mode only,
+               /// use output data rates lower than 100 Hz in low noise mode)
+               pub $($async)? fn low_noise(&mut self, flag: bool) -> Result<(), Error<E>> {
+                    match flag {
+                         true => self.set_register_bit_flag(lps22hh::Registers::CTRL_REG2.addr(), lps22hh::Bitmasks::LOW_NOISE_EN) $(.$await)?,
+                         false => self.clear_register_bit_flag(lps22hh::Registers::CTRL_REG2.addr(), lps22hh::Bitmasks::LOW_NOISE_EN) $(.$await)?,
+                    }
+               }
+          }
+
+          #[cfg(any(feature = "lps22df", feature = "lps28dfw"))]
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant<ODR = lps22df::ODR>,
+               MODE: PowerMode,
+               {
+               /// Pressure and temperature internal average configuration, common to LPS22DF and LPS28DFW
+               $($async)? fn set_lps22df_averaging(&mut self, averaging: AVG) -> Result<(), Error<E>> {
+                    let mut payload = self.read_register(lps22df::Registers::CTRL_REG1.addr()) $(.$await)? ?;
+                    payload &= !lps22df::Bitmasks::AVG_MASK;
+                    payload |= averaging.value();
+                    self.write_register(lps22df::Registers::CTRL_REG1.addr(), payload) $(.$await)?
+               }
+          }
+
+          #[cfg(feature = "lps22df")]
+          impl<T, MODE, $($pin,)? E> $driver<T, LPS22DF, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               MODE: PowerMode,
+               {
+               /// Pressure and temperature internal average configuration (default 4).
+               pub $($async)? fn averaging(&mut self, averaging: AVG) -> Result<(), Error<E>> {
+                    self.set_lps22df_averaging(averaging) $(.$await)?
+               }
+          }
+
+          #[cfg(feature = "lps28dfw")]
+          impl<T, MODE, $($pin,)? E> $driver<T, LPS28DFW, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               MODE: PowerMode,
+               {
+               /// Pressure and temperature internal average configuration (default 4).
+               pub $($async)? fn averaging(&mut self, averaging: AVG) -> Result<(), Error<E>> {
+                    self.set_lps22df_averaging(averaging) $(.$await)?
+               }
+          }
+
+          #[cfg(feature = "lps28dfw")]
+          impl<T, $($pin,)? E> $driver<T, LPS28DFW, PowerDown $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               {
+               /// Select the full-scale mode (can be changed in power-down mode only),
+               /// the pressure readings use the sensitivity of the selected mode.
+               pub $($async)? fn full_scale(&mut self, full_scale: FS_MODE) -> Result<(), Error<E>> {
+                    let mut payload = self.read_register(lps22df::Registers::CTRL_REG2.addr()) $(.$await)? ?;
+                    payload &= !lps22df::Bitmasks::FS_MODE;
+                    payload |= full_scale.value();
+                    self.write_register(lps22df::Registers::CTRL_REG2.addr(), payload) $(.$await)? ?;
+                    self.press_sensitivity = full_scale.sensitivity();
+                    Ok(())
+               }
+          }
+     };
+}
+
+impl_config!(LPS2X, Interface; ;);
+#[cfg(feature = "async")]
+impl_config!(LPS2XAsync<P>, AsyncInterface; async; await);
+
+#[cfg(all(test, feature = "lps28dfw"))]
+mod tests {
+     use super::*;
+     use mock::RegisterFile;
+
+     /// LPS28DFW with the pressure output holding 4_096_000 counts: 1000 hPa in 1260 hPa full-scale mode,
+     /// 2000 hPa in 4060 hPa mode
+     fn sensor() -> LPS2X<RegisterFile, LPS28DFW> {
+          let mut registers = RegisterFile::new();
+          registers.registers[LPS28DFW::PRESS_OUT_XL as usize..][..3].copy_from_slice(&[0x00, 0x80, 0x3E]);
+          registers.driver(LPS28DFW)
      }
 
-     #[cfg(feature = "lps22hb")]
-     /// Enable and configure low-pass filter on pressure data in Continuous mode
-     pub fn lowpass_filter(&mut self, enable: bool, configure: bool) -> Result<(), T::Error> {
-          match enable {
-               true => self.set_register_bit_flag(Registers::CTRL_REG1, Bitmasks::EN_LPFP),
-               false => self.clear_register_bit_flag(Registers::CTRL_REG1, Bitmasks::EN_LPFP),
-          }?;
-          match configure {
-               true => self.set_register_bit_flag(Registers::CTRL_REG1, Bitmasks::LPFP_CFG),
-               false => self.clear_register_bit_flag(Registers::CTRL_REG1, Bitmasks::LPFP_CFG),
-          }?;
-          Ok(())
+     #[test]
+     fn software_reset_restores_the_default_sensitivity() {
+          let mut sensor = sensor();
+          sensor.full_scale(FS_MODE::_4060hPa).unwrap();
+          let mut one_shot: LPS2X<_, _, OneShot> = sensor.into_mode();
+          assert_eq!(one_shot.read_pressure().unwrap(), Pressure::from_hectopascal(2000));
+
+          // the SWRESET bit is cleared once the reset is done
+          one_shot.interface.script(LPS28DFW::CTRL_REG2, &[lps22df::Bitmasks::FS_MODE, 0]);
+          let sensor = one_shot.software_reset().map_err(|(_, error)| error).unwrap();
+          let mut one_shot: LPS2X<_, _, OneShot> = sensor.into_mode();
+          assert_eq!(one_shot.read_pressure().unwrap(), Pressure::from_hectopascal(1000));
      }
 
-     #[cfg(feature = "lps22hb")]
-     /// Reset low-pass filter.  If the LPFP is active, in order to avoid the transitory phase,
-     /// the filter can be reset by reading this register before generating pressure measurements.
-     pub fn lowpass_filter_reset(&mut self) -> Result<(), T::Error> {
-          let mut _data = [0u8; 1];
-          self.interface
-               .read(Registers::LPFP_RES.addr(), &mut _data)?;
-          Ok(())
+     #[test]
+     fn read_config_syncs_the_sensitivity_with_the_full_scale_mode() {
+          let mut sensor = sensor();
+          sensor.interface.registers[LPS28DFW::CTRL_REG2 as usize] = lps22df::Bitmasks::FS_MODE;
+          let config = sensor.read_config().unwrap();
+          assert!(matches!(config.sensor.full_scale, FS_MODE::_4060hPa));
+
+          let mut one_shot: LPS2X<_, _, OneShot> = sensor.into_mode();
+          assert_eq!(one_shot.read_pressure().unwrap(), Pressure::from_hectopascal(2000));
      }
 }
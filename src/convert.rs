@@ -0,0 +1,302 @@
+//! Conversions between register contents and physical values
+//!
+//! The driver uses these functions for all its readings and settings; they are public so that
+//! register contents acquired outside the driver (e.g. by DMA) are converted the same way.
+//! The register contents are given least significant byte first, as they are read with
+//! address auto-increment.
+//!
+//! The pressure conversions take the pressure sensitivity in LSB/hPa: [`PRESS_SENSITIVITY`](crate::PRESS_SENSITIVITY),
+//! or the sensitivity of the full-scale mode on LPS28DFW (`lps28dfw::FS_MODE::sensitivity()`),
+//! as returned by `pressure_sensitivity()` on the driver.
+
+use crate::sensor::{DataStatus, Measurement};
+use crate::units::{Pressure, Temperature};
+use crate::Variant;
+
+/// Pressure counts from the content of the PRESS_OUT registers, sign-extended from 24 bits
+/// (the pressure is negative e.g. in differential mode).
+///
+/// ```
+/// use lps2x::convert::pressure_counts;
+///
+/// assert_eq!(pressure_counts([0x00, 0x50, 0x3F]), 4_149_248);
+/// assert_eq!(pressure_counts([0x00, 0xF0, 0xFF]), -4096);
+/// ```
+pub fn pressure_counts(data: [u8; 3]) -> i32 {
+    i32::from_le_bytes([0, data[0], data[1], data[2]]) >> 8
+}
+
+/// Temperature counts from the content of the TEMP_OUT registers
+///
+/// ```
+/// use lps2x::convert::temperature_counts;
+///
+/// assert_eq!(temperature_counts([0xC4, 0x09]), 2500);
+/// assert_eq!(temperature_counts([0x9C, 0xFF]), -100);
+/// ```
+pub fn temperature_counts(data: [u8; 2]) -> i16 {
+    i16::from_le_bytes(data)
+}
+
+/// Temperature in hundredths of a degree Celsius from the temperature counts (rounded toward zero),
+/// the offset is added before the division so that the rounding applies to the temperature
+pub(crate) fn temperature_centidegrees<C: Variant>(counts: i16) -> i16 {
+    let scale = C::TEMP_SCALE as i32;
+    ((counts as i32 * 100 + C::TEMP_OFFSET_CENTI as i32 * scale) / scale) as i16
+}
+
+/// Pressure from the pressure counts (rounded toward zero to a hundredth of a pascal)
+///
+/// ```
+/// use lps2x::convert::pressure_from_counts;
+/// use lps2x::PRESS_SENSITIVITY;
+///
+/// assert_eq!(pressure_from_counts(4_149_248, PRESS_SENSITIVITY).as_centipascal(), 10_130_000);
+/// // LPS28DFW in 4060 hPa full-scale mode
+/// assert_eq!(pressure_from_counts(2_074_624, 2048).as_centipascal(), 10_130_000);
+/// ```
+pub fn pressure_from_counts(counts: i32, sensitivity: u16) -> Pressure {
+    Pressure::from_counts(counts, sensitivity)
+}
+
+/// Temperature of the sensor model `C` from the temperature counts (rounded toward zero to a hundredth of a degree)
+///
+/// ```
+/// # #[cfg(all(feature = "lps22hb", feature = "lps25hb"))] {
+/// use lps2x::convert::temperature_from_counts;
+/// use lps2x::{LPS22HB, LPS25HB};
+///
+/// assert_eq!(temperature_from_counts::<LPS22HB>(2500).as_centidegrees(), 2500);
+/// // LPS25HB: 480 LSB/°C, 42.5 °C offset
+/// assert_eq!(temperature_from_counts::<LPS25HB>(-8400).as_centidegrees(), 2500);
+/// # }
+/// ```
+pub fn temperature_from_counts<C: Variant>(counts: i16) -> Temperature {
+    Temperature::from_centidegrees(temperature_centidegrees::<C>(counts) as i32)
+}
+
+/// Pressure from the content of the PRESS_OUT registers (PRESS_OUT_XL, PRESS_OUT_L, PRESS_OUT_H)
+///
+/// ```
+/// use lps2x::convert::pressure_from_bytes;
+/// use lps2x::PRESS_SENSITIVITY;
+///
+/// assert_eq!(pressure_from_bytes([0x00, 0x50, 0x3F], PRESS_SENSITIVITY).as_pascal(), 101_300);
+/// assert_eq!(pressure_from_bytes([0x00, 0xF0, 0xFF], PRESS_SENSITIVITY).as_pascal(), -100);
+/// ```
+pub fn pressure_from_bytes(data: [u8; 3], sensitivity: u16) -> Pressure {
+    pressure_from_counts(pressure_counts(data), sensitivity)
+}
+
+/// Temperature of the sensor model `C` from the content of the TEMP_OUT registers (TEMP_OUT_L, TEMP_OUT_H)
+///
+/// ```
+/// # #[cfg(all(feature = "lps22hb", feature = "lps25hb"))] {
+/// use lps2x::convert::temperature_from_bytes;
+/// use lps2x::{LPS22HB, LPS25HB};
+///
+/// assert_eq!(temperature_from_bytes::<LPS22HB>([0xC4, 0x09]).as_centidegrees(), 2500);
+/// assert_eq!(temperature_from_bytes::<LPS25HB>([0x30, 0xDF]).as_centidegrees(), 2500);
+/// # }
+/// ```
+pub fn temperature_from_bytes<C: Variant>(data: [u8; 2]) -> Temperature {
+    temperature_from_counts::<C>(temperature_counts(data))
+}
+
+/// Measurement of the sensor model `C` from the content of the STATUS, PRESS_OUT and TEMP_OUT registers,
+/// read in one transaction starting from STATUS (see `read_measurement()`)
+///
+/// ```
+/// # #[cfg(feature = "lps22hb")] {
+/// use lps2x::convert::measurement_from_bytes;
+/// use lps2x::{LPS22HB, PRESS_SENSITIVITY};
+///
+/// let measurement = measurement_from_bytes::<LPS22HB>([0x03, 0x00, 0x50, 0x3F, 0xC4, 0x09], PRESS_SENSITIVITY);
+/// assert_eq!(measurement.pressure.as_pascal(), 101_300);
+/// assert_eq!(measurement.temperature.as_centidegrees(), 2500);
+/// assert!(measurement.status.press_available && measurement.status.temp_available);
+/// # }
+/// ```
+pub fn measurement_from_bytes<C: Variant>(data: [u8; 6], sensitivity: u16) -> Measurement {
+    Measurement {
+        pressure: pressure_from_bytes([data[1], data[2], data[3]], sensitivity),
+        temperature: temperature_from_bytes::<C>([data[4], data[5]]),
+        status: DataStatus::from_register::<C>(data[0]),
+    }
+}
+
+/// Reference pressure of the sensor model `C` from the content of the REF_P registers
+/// (REF_P_XL, REF_P_L, REF_P_H). On the models with a 16-bit REF_P register (`C::REF_P_LEN` is 2,
+/// REF_P_L and REF_P_H hold the pressure counts / 256) the first byte is ignored.
+///
+/// ```
+/// # #[cfg(all(feature = "lps22hb", feature = "lps22hh"))] {
+/// use lps2x::convert::reference_pressure_from_bytes;
+/// use lps2x::{LPS22HB, LPS22HH, PRESS_SENSITIVITY};
+///
+/// assert_eq!(reference_pressure_from_bytes::<LPS22HB>([0x00, 0x50, 0x3F], PRESS_SENSITIVITY).as_pascal(), 101_300);
+/// assert_eq!(reference_pressure_from_bytes::<LPS22HH>([0xFF, 0x50, 0x3F], PRESS_SENSITIVITY).as_pascal(), 101_300);
+/// # }
+/// ```
+pub fn reference_pressure_from_bytes<C: Variant>(data: [u8; 3], sensitivity: u16) -> Pressure {
+    let lsb = if C::REF_P_LEN == 3 { data[0] } else { 0 };
+    pressure_from_bytes([lsb, data[1], data[2]], sensitivity)
+}
+
+/// Content of the REF_P registers of the sensor model `C` for the reference pressure (rounded toward zero),
+/// see `reference_pressure_from_bytes()`; the first byte is 0 on the models with a 16-bit REF_P register.
+/// Returns `None` if the pressure doesn't fit in the registers.
+///
+/// ```
+/// # #[cfg(all(feature = "lps22hb", feature = "lps22hh"))] {
+/// use lps2x::convert::reference_pressure_to_bytes;
+/// use lps2x::units::Pressure;
+/// use lps2x::{LPS22HB, LPS22HH, PRESS_SENSITIVITY};
+///
+/// let pressure = Pressure::from_hectopascal(1013);
+/// assert_eq!(reference_pressure_to_bytes::<LPS22HB>(pressure, PRESS_SENSITIVITY), Some([0x00, 0x50, 0x3F]));
+/// assert_eq!(reference_pressure_to_bytes::<LPS22HH>(pressure, PRESS_SENSITIVITY), Some([0x00, 0x50, 0x3F]));
+/// assert_eq!(reference_pressure_to_bytes::<LPS22HH>(Pressure::from_hectopascal(-1), PRESS_SENSITIVITY), Some([0x00, 0xF0, 0xFF]));
+/// assert_eq!(reference_pressure_to_bytes::<LPS22HH>(Pressure::from_hectopascal(2048), PRESS_SENSITIVITY), None);
+/// # }
+/// ```
+pub fn reference_pressure_to_bytes<C: Variant>(pressure: Pressure, sensitivity: u16) -> Option<[u8; 3]> {
+    let counts = pressure.to_counts(sensitivity);
+    let bytes = match C::REF_P_LEN {
+        // REF_P is a 24-bit two's complement value
+        3 if (-0x80_0000..0x80_0000).contains(&counts) => (counts as i32).to_le_bytes(),
+        // 16-bit REF_P, two's complement, holding the counts / 256
+        2 if (-0x80_0000..0x80_0000).contains(&counts) => ((counts as i32) / 256 * 256).to_le_bytes(),
+        _ => return None,
+    };
+    Some([bytes[0], bytes[1], bytes[2]])
+}
+
+/// Pressure from the 16-bit value of the THS_P or RPDS registers, which hold the pressure counts / 256
+/// (the value in hPa x 16, or x 8 on LPS28DFW in 4060 hPa full-scale mode)
+fn pressure_from_register(value: i32, sensitivity: u16) -> Pressure {
+    Pressure::from_counts(value * 256, sensitivity)
+}
+
+/// Value of the THS_P or RPDS registers for the pressure (rounded toward zero), see `pressure_from_register()`
+fn pressure_to_register(pressure: Pressure, sensitivity: u16) -> i64 {
+    pressure.to_counts(sensitivity) / 256
+}
+
+/// Interrupt threshold from the content of the THS_P registers (THS_P_L, THS_P_H), an unsigned value
+///
+/// ```
+/// use lps2x::convert::threshold_from_bytes;
+/// use lps2x::PRESS_SENSITIVITY;
+///
+/// assert_eq!(threshold_from_bytes([0xA0, 0x00], PRESS_SENSITIVITY).as_pascal(), 1000);
+/// ```
+pub fn threshold_from_bytes(data: [u8; 2], sensitivity: u16) -> Pressure {
+    pressure_from_register(u16::from_le_bytes(data) as i32, sensitivity)
+}
+
+/// Content of the THS_P registers for the interrupt threshold (rounded toward zero).
+/// Returns `None` if the threshold is negative or doesn't fit in the registers.
+///
+/// ```
+/// use lps2x::convert::threshold_to_bytes;
+/// use lps2x::units::Pressure;
+/// use lps2x::PRESS_SENSITIVITY;
+///
+/// assert_eq!(threshold_to_bytes(Pressure::from_hectopascal(10), PRESS_SENSITIVITY), Some([0xA0, 0x00]));
+/// // LPS28DFW in 4060 hPa full-scale mode
+/// assert_eq!(threshold_to_bytes(Pressure::from_hectopascal(10), 2048), Some([0x50, 0x00]));
+/// assert_eq!(threshold_to_bytes(Pressure::from_hectopascal(-10), PRESS_SENSITIVITY), None);
+/// ```
+pub fn threshold_to_bytes(threshold: Pressure, sensitivity: u16) -> Option<[u8; 2]> {
+    let value = pressure_to_register(threshold, sensitivity);
+    if (0..=u16::MAX as i64).contains(&value) {
+        Some((value as u16).to_le_bytes())
+    } else {
+        None
+    }
+}
+
+/// Pressure offset from the content of the RPDS registers (RPDS_L, RPDS_H), a signed value
+///
+/// ```
+/// use lps2x::convert::pressure_offset_from_bytes;
+/// use lps2x::PRESS_SENSITIVITY;
+///
+/// assert_eq!(pressure_offset_from_bytes([0xE0, 0xFF], PRESS_SENSITIVITY).as_pascal(), -200);
+/// ```
+pub fn pressure_offset_from_bytes(data: [u8; 2], sensitivity: u16) -> Pressure {
+    pressure_from_register(i16::from_le_bytes(data) as i32, sensitivity)
+}
+
+/// Content of the RPDS registers for the pressure offset (rounded toward zero).
+/// Returns `None` if the offset doesn't fit in the registers.
+///
+/// ```
+/// use lps2x::convert::pressure_offset_to_bytes;
+/// use lps2x::units::Pressure;
+/// use lps2x::PRESS_SENSITIVITY;
+///
+/// assert_eq!(pressure_offset_to_bytes(Pressure::from_hectopascal(-2), PRESS_SENSITIVITY), Some([0xE0, 0xFF]));
+/// assert_eq!(pressure_offset_to_bytes(Pressure::from_hectopascal(3000), PRESS_SENSITIVITY), None);
+/// ```
+pub fn pressure_offset_to_bytes(offset: Pressure, sensitivity: u16) -> Option<[u8; 2]> {
+    let value = pressure_to_register(offset, sensitivity);
+    if (i16::MIN as i64..=i16::MAX as i64).contains(&value) {
+        Some((value as i16).to_le_bytes())
+    } else {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "lps22hb", feature = "lps22hh"))]
+mod tests {
+    use super::*;
+    use crate::{LPS22HB, LPS22HH, PRESS_SENSITIVITY};
+
+    fn round_trip<C: Variant>(pressure: Pressure) -> Option<Pressure> {
+        reference_pressure_to_bytes::<C>(pressure, PRESS_SENSITIVITY)
+            .map(|data| reference_pressure_from_bytes::<C>(data, PRESS_SENSITIVITY))
+    }
+
+    #[test]
+    fn reference_pressure_round_trip_with_24_bit_ref_p() {
+        assert_eq!(LPS22HB::REF_P_LEN, 3);
+        for hpa in [-2048, -1013, -1, 0, 1, 1013, 2047] {
+            let pressure = Pressure::from_hectopascal(hpa);
+            assert_eq!(round_trip::<LPS22HB>(pressure), Some(pressure));
+        }
+        let pressure = Pressure::from_centipascal(-10_132_500);
+        assert_eq!(round_trip::<LPS22HB>(pressure), Some(pressure));
+        assert_eq!(round_trip::<LPS22HB>(Pressure::from_hectopascal(2048)), None);
+        assert_eq!(round_trip::<LPS22HB>(Pressure::from_hectopascal(-2049)), None);
+    }
+
+    #[test]
+    fn reference_pressure_round_trip_with_16_bit_ref_p() {
+        assert_eq!(LPS22HH::REF_P_LEN, 2);
+        for hpa in [-2048, -1013, -1, 0, 1, 1013, 2047] {
+            let pressure = Pressure::from_hectopascal(hpa);
+            assert_eq!(round_trip::<LPS22HH>(pressure), Some(pressure));
+        }
+        // 1/16 hPa resolution, rounded toward zero
+        let pressure = Pressure::from_counts(-0x12_3457, PRESS_SENSITIVITY);
+        assert_eq!(round_trip::<LPS22HH>(pressure), Some(Pressure::from_counts(-0x12_3400, PRESS_SENSITIVITY)));
+        let pressure = Pressure::from_counts(0x12_3457, PRESS_SENSITIVITY);
+        assert_eq!(round_trip::<LPS22HH>(pressure), Some(Pressure::from_counts(0x12_3400, PRESS_SENSITIVITY)));
+        assert_eq!(round_trip::<LPS22HH>(Pressure::from_hectopascal(2048)), None);
+        assert_eq!(round_trip::<LPS22HH>(Pressure::from_hectopascal(-2049)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "lps25hb")]
+    fn temperature_with_offset_is_rounded_toward_zero() {
+        use crate::LPS25HB;
+        // 42.5 °C - 8401 / 480 °C = 24.9979 °C
+        assert_eq!(temperature_centidegrees::<LPS25HB>(-8401), 2499);
+        // 42.5 °C - 20410 / 480 °C = -0.0208 °C
+        assert_eq!(temperature_centidegrees::<LPS25HB>(-20410), -2);
+        // 42.5 °C + 5 / 480 °C = 42.5104 °C
+        assert_eq!(temperature_centidegrees::<LPS25HB>(5), 4251);
+    }
+}
@@ -0,0 +1,195 @@
+//! Sensor model detection
+//!
+//! All the supported models have the WHO_AM_I register at the same address,
+//! so the model can be identified before choosing the register map.
+//! Some models have the same ID and can't be told apart by reading the sensor:
+//! - LPS22HB, LPS33HW and LPS35HW (0xB1)
+//! - LPS22DF and LPS28DFW (0xB4)
+//!
+//! `Device::detect()` reports a shared ID as the model of the family if only one of them is enabled,
+//! and returns `Error::AmbiguousDevice` with the ID otherwise.
+//! Use `Device::detect_as()` to select the model used for a shared ID,
+//! e.g. `Device::detect_as(interface, LPS33HW)` on a board fitted with a LPS33HW or a LPS25HB.
+
+use super::*;
+
+/// WHO_AM_I register address, common to all the supported models
+const WHO_AM_I: u8 = 0x0F;
+
+/// Sensor model that can be selected with `Device::detect_as()`
+pub trait DeviceModel: Variant + Sized {
+    /// Wrap the driver instance in the `Device` variant of the model
+    fn device<T>(sensor: LPS2X<T, Self>) -> Device<T>;
+    /// Wrap the driver instance in the `DeviceAsync` variant of the model
+    #[cfg(feature = "async")]
+    fn device_async<T>(sensor: LPS2XAsync<T, Self>) -> DeviceAsync<T>;
+}
+
+macro_rules! impl_device_model {
+    ($model:ident, $feature:literal) => {
+        #[cfg(feature = $feature)]
+        impl DeviceModel for $model {
+            fn device<T>(sensor: LPS2X<T, Self>) -> Device<T> {
+                Device::$model(sensor)
+            }
+
+            #[cfg(feature = "async")]
+            fn device_async<T>(sensor: LPS2XAsync<T, Self>) -> DeviceAsync<T> {
+                DeviceAsync::$model(sensor)
+            }
+        }
+    };
+}
+
+impl_device_model!(LPS22HB, "lps22hb");
+impl_device_model!(LPS22HH, "lps22hh");
+impl_device_model!(LPS22DF, "lps22df");
+impl_device_model!(LPS25HB, "lps25hb");
+impl_device_model!(LPS28DFW, "lps28dfw");
+impl_device_model!(LPS33HW, "lps33hw");
+impl_device_model!(LPS35HW, "lps35hw");
+
+macro_rules! impl_device {
+    ($device:ident, $driver:ident, $interface:ident, $into:ident, $doc:expr; $($async:ident)?; $($await:ident)?) => {
+        #[doc = $doc]
+        pub enum $device<T> {
+            #[cfg(feature = "lps22hb")]
+            /// LPS22HB found (WHO_AM_I = 0xB1, only if the `lps33hw` and `lps35hw` features are not enabled)
+            LPS22HB($driver<T, LPS22HB>),
+            #[cfg(feature = "lps22hh")]
+            /// LPS22HH found (WHO_AM_I = 0xB3)
+            LPS22HH($driver<T, LPS22HH>),
+            #[cfg(feature = "lps22df")]
+            /// LPS22DF found (WHO_AM_I = 0xB4, only if the `lps28dfw` feature is not enabled)
+            LPS22DF($driver<T, LPS22DF>),
+            #[cfg(feature = "lps25hb")]
+            /// LPS25HB found (WHO_AM_I = 0xBD)
+            LPS25HB($driver<T, LPS25HB>),
+            #[cfg(feature = "lps28dfw")]
+            /// LPS28DFW found (WHO_AM_I = 0xB4, only if the `lps22df` feature is not enabled)
+            LPS28DFW($driver<T, LPS28DFW>),
+            #[cfg(feature = "lps33hw")]
+            /// LPS33HW found (WHO_AM_I = 0xB1, only if the `lps22hb` and `lps35hw` features are not enabled)
+            LPS33HW($driver<T, LPS33HW>),
+            #[cfg(feature = "lps35hw")]
+            /// LPS35HW found (WHO_AM_I = 0xB1, only if the `lps22hb` and `lps33hw` features are not enabled)
+            LPS35HW($driver<T, LPS35HW>),
+        }
+
+        impl<T, E> $device<T>
+        where
+            T: $interface<Error = E>,
+        {
+            /// Read the WHO_AM_I register and create a driver instance for the matching sensor model.
+            /// Returns `Error::UnknownDevice` with the ID found if it doesn't belong to any of the enabled models,
+            /// and `Error::AmbiguousDevice` if it is shared by several of them (see `detect_as()`).
+            pub $($async)? fn detect(mut interface: T) -> Result<Self, Error<E>> {
+                let whoami = Self::read_device_id(&mut interface) $(.$await)? ?;
+                Self::from_device_id(interface, whoami)
+            }
+
+            /// Read the WHO_AM_I register and create a driver instance for `model` if it has the ID found,
+            /// e.g. to drive a LPS33HW with the `lps22hb` feature enabled, which `detect()` can't tell apart.
+            /// Other IDs are handled as by `detect()`.
+            pub $($async)? fn detect_as<M: DeviceModel>(mut interface: T, model: M) -> Result<Self, Error<E>> {
+                let whoami = Self::read_device_id(&mut interface) $(.$await)? ?;
+                if whoami == M::WHOAMI {
+                    return Ok(M::$into($driver::new(interface, model)));
+                }
+                Self::from_device_id(interface, whoami)
+            }
+
+            $($async)? fn read_device_id(interface: &mut T) -> Result<u8, Error<E>> {
+                let mut data = [0u8];
+                interface.read(WHO_AM_I, &mut data) $(.$await)? .map_err(Error::Interface)?;
+                Ok(data[0])
+            }
+
+            /// Driver instance for the enabled model with the ID `whoami`
+            // `interface` is unused if all the enabled models share their ID
+            #[allow(unused_variables)]
+            fn from_device_id(interface: T, whoami: u8) -> Result<Self, Error<E>> {
+                match whoami {
+                    #[cfg(all(feature = "lps22hb", not(any(feature = "lps33hw", feature = "lps35hw"))))]
+                    <LPS22HB as Variant>::WHOAMI => Ok($device::LPS22HB($driver::new(interface, LPS22HB))),
+                    #[cfg(feature = "lps22hh")]
+                    <LPS22HH as Variant>::WHOAMI => Ok($device::LPS22HH($driver::new(interface, LPS22HH))),
+                    #[cfg(all(feature = "lps22df", not(feature = "lps28dfw")))]
+                    <LPS22DF as Variant>::WHOAMI => Ok($device::LPS22DF($driver::new(interface, LPS22DF))),
+                    #[cfg(feature = "lps25hb")]
+                    <LPS25HB as Variant>::WHOAMI => Ok($device::LPS25HB($driver::new(interface, LPS25HB))),
+                    // same ID as LPS22DF
+                    #[cfg(all(feature = "lps28dfw", not(feature = "lps22df")))]
+                    <LPS28DFW as Variant>::WHOAMI => Ok($device::LPS28DFW($driver::new(interface, LPS28DFW))),
+                    // same ID as LPS22HB
+                    #[cfg(all(feature = "lps33hw", not(any(feature = "lps22hb", feature = "lps35hw"))))]
+                    <LPS33HW as Variant>::WHOAMI => Ok($device::LPS33HW($driver::new(interface, LPS33HW))),
+                    #[cfg(all(feature = "lps35hw", not(any(feature = "lps22hb", feature = "lps33hw"))))]
+                    <LPS35HW as Variant>::WHOAMI => Ok($device::LPS35HW($driver::new(interface, LPS35HW))),
+                    // shared ID, more than one of its models enabled
+                    #[cfg(any(
+                        all(feature = "lps22hb", feature = "lps33hw"),
+                        all(feature = "lps22hb", feature = "lps35hw"),
+                        all(feature = "lps33hw", feature = "lps35hw")
+                    ))]
+                    variant::lps22hb::WHOAMI => Err(Error::AmbiguousDevice(whoami)),
+                    #[cfg(all(feature = "lps22df", feature = "lps28dfw"))]
+                    <LPS22DF as Variant>::WHOAMI => Err(Error::AmbiguousDevice(whoami)),
+                    whoami => Err(Error::UnknownDevice(whoami)),
+                }
+            }
+
+            /// Destroy driver instance, return interface instance.
+            pub fn destroy(self) -> T {
+                match self {
+                    #[cfg(feature = "lps22hb")]
+                    $device::LPS22HB(sensor) => sensor.interface,
+                    #[cfg(feature = "lps22hh")]
+                    $device::LPS22HH(sensor) => sensor.interface,
+                    #[cfg(feature = "lps22df")]
+                    $device::LPS22DF(sensor) => sensor.interface,
+                    #[cfg(feature = "lps25hb")]
+                    $device::LPS25HB(sensor) => sensor.interface,
+                    #[cfg(feature = "lps28dfw")]
+                    $device::LPS28DFW(sensor) => sensor.interface,
+                    #[cfg(feature = "lps33hw")]
+                    $device::LPS33HW(sensor) => sensor.interface,
+                    #[cfg(feature = "lps35hw")]
+                    $device::LPS35HW(sensor) => sensor.interface,
+                }
+            }
+        }
+    };
+}
+
+impl_device!(Device, LPS2X, Interface, device, "Driver instance for the sensor model found by [`Device::detect()`]"; ;);
+#[cfg(feature = "async")]
+impl_device!(DeviceAsync, LPS2XAsync, AsyncInterface, device_async, "Async driver instance for the sensor model found by [`DeviceAsync::detect()`]"; async; await);
+
+#[cfg(all(test, feature = "lps22hb", feature = "lps22hh", feature = "lps33hw"))]
+mod tests {
+    use super::*;
+    use mock::RegisterFile;
+
+    fn sensor(whoami: u8) -> RegisterFile {
+        let mut registers = RegisterFile::new();
+        registers.registers[WHO_AM_I as usize] = whoami;
+        registers
+    }
+
+    #[test]
+    fn detect_as_selects_the_model_for_a_shared_device_id() {
+        assert!(matches!(Device::detect_as(sensor(0xB1), LPS33HW), Ok(Device::LPS33HW(_))));
+        // a different ID is reported as by detect()
+        assert!(matches!(Device::detect_as(sensor(0xB3), LPS33HW), Ok(Device::LPS22HH(_))));
+        assert!(matches!(Device::detect_as(sensor(0x00), LPS33HW), Err(Error::UnknownDevice(0x00))));
+    }
+
+    #[test]
+    fn detect_reports_a_device_id_shared_by_several_enabled_models() {
+        assert!(matches!(Device::detect(sensor(0xB1)), Err(Error::AmbiguousDevice(0xB1))));
+        assert!(matches!(Device::detect_as(sensor(0xB1), LPS22HB), Ok(Device::LPS22HB(_))));
+        // a single model enabled for the ID
+        assert!(matches!(Device::detect(sensor(0xB3)), Ok(Device::LPS22HH(_))));
+    }
+}
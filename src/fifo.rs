@@ -1,91 +1,153 @@
 //! Various functions related to FIFO
 //!
 //! TO DO: improve watermark level reading (?)
- 
+
 use super::*;
- 
+use convert::{pressure_from_bytes, temperature_from_bytes};
+use interrupt::{IntStatus, InterruptConfig};
+use sensor::{DataStatus, Measurement};
+#[cfg(feature = "lps25hb")]
+use variant::lps25hb::{self, FIFO_MEAN};
+#[cfg(feature = "lps25hb")]
+use mode::Continuous;
+#[cfg(feature = "lps25hb")]
+use embedded_hal::delay::DelayNs;
+#[cfg(all(feature = "lps25hb", feature = "async"))]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+
 /// FIFO settings
 #[derive(Debug)]
-pub struct FIFOConfig {
+pub struct FIFOConfig<C: Variant> {
 
     /// Stop on FIFO watermark (enable FIFO watermark use)
     pub enable_watermark: FLAG, // default disabled
-    /// Select FIFO operation mode (see Table 22 for details)        
-    pub fifo_mode: FIFO_MODE, // default Bypass
+    /// Select FIFO operation mode (see the FIFO_MODE table of the sensor model for details)
+    pub fifo_mode: C::FIFO_MODE, // default Bypass
     /// Set the watermark level
-    pub watermark_level: u8, // default 0
+    pub watermark_level: u8, // default 0, must be lower than the FIFO depth
     #[cfg(feature="lps25hb")]
-    /// Enable decimating output pressure to 1Hz with FIFO Mean mode
+    /// Enable decimating output pressure to 1Hz with FIFO Mean mode (LPS25HB only)
     pub enable_decimating: FLAG, // default disabled
     #[cfg(feature="lps25hb")]
-    /// Select sample size for FIFO Mean mode running average (see Table 23 for details)        
+    /// Select sample size for FIFO Mean mode running average (LPS25HB only, see Table 23 for details)
     pub fifo_mean_config: FIFO_MEAN, // default 2-sample
 }
- 
-impl Default for FIFOConfig {
+
+impl<C: Variant> Default for FIFOConfig<C> {
     fn default() -> Self {
         FIFOConfig {
             enable_watermark: FLAG::Disabled,      // disabled
-            fifo_mode: FIFO_MODE::Bypass, // Bypass mode
-            watermark_level: 32u8,        // default 32, full FIFO
-            
+            fifo_mode: C::FIFO_MODE::default(), // Bypass mode
+            watermark_level: 0u8,         // default 0
+
             #[cfg(feature = "lps25hb")]
             enable_decimating: FLAG::Disabled,              // disabled
-            #[cfg(feature = "lps25hb")]            
+            #[cfg(feature = "lps25hb")]
             fifo_mean_config: FIFO_MEAN::_2sample, // 2 samples
         }
     }
 }
 
-#[cfg(feature = "lps25hb")] 
-impl FIFOConfig {
-    /// Returns values to be written to CTRL_REG2 and FIFO_CTRL:
-    fn f_ctrl_reg2(&self) -> u8 {
-        let mut data = 0u8;
-        // THIS RESULT MUST THEN BE COMBINED WITH THE OTHER BIT SETTINGS
-        if self.enable_watermark.status() {
-            data |= 1 << 5;
-        }
-        if self.enable_decimating.status() {
-            data |= 1 << 4;
+/// FIFO modes switching on the interrupt event (IA bit of INT_SOURCE), used for trigger capture
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FIFO_TRIGGER {
+    /// Stream mode until the event, then FIFO mode: the FIFO holds the samples before and after the event
+    #[default]
+    Stream_to_FIFO,
+    /// Bypass mode until the event, then Stream mode: the FIFO holds the latest samples after the event
+    Bypass_to_stream,
+    /// Bypass mode until the event, then FIFO mode: the FIFO holds the first samples after the event
+    Bypass_to_FIFO,
+}
+
+/// Latch, low and high pressure event bits of INTERRUPT_CFG, the same on all the models
+pub(crate) const INTERRUPT_EVENTS_MASK: u8 = 0b0000_0111;
+
+/// Trigger capture settings, see `arm_capture()`
+#[derive(Debug)]
+pub struct CaptureConfig {
+    /// FIFO mode switching on the pressure event
+    pub trigger_mode: FIFO_TRIGGER, // default Stream-to-FIFO
+    /// Threshold of the pressure event, relative to the reference pressure (see `set_threshold()`)
+    pub threshold: Pressure, // default 0
+    /// Trigger on low pressure event
+    pub enable_low_event: FLAG, // default disabled
+    /// Trigger on high pressure event
+    pub enable_high_event: FLAG, // default enabled
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        CaptureConfig {
+            trigger_mode: FIFO_TRIGGER::default(),    // Stream-to-FIFO
+            threshold: Pressure::default(),           // 0
+            enable_low_event: FLAG::Disabled,         // disabled
+            enable_high_event: FLAG::Enabled,         // enabled
         }
-        data
     }
-    fn f_fifo_ctrl(&self) -> u8 {
-        let mut data = 0u8;
-
-        data |= self.fifo_mode.value();
+}
 
-        let wtm = match self.fifo_mode {
-            FIFO_MODE::FIFO_Mean => self.fifo_mean_config.value(),
-            _ => self.watermark_level,
-        };
-        data |= wtm;
-        data
+impl CaptureConfig {
+    /// Interrupt request bits of INTERRUPT_CFG: the event is latched, so that it holds the FIFO mode
+    /// until INT_SOURCE is read
+    pub(crate) fn interrupt_cfg(&self) -> u8 {
+        InterruptConfig {
+            enable_latch_interrupt: FLAG::Enabled,
+            enable_low_event: self.enable_low_event,
+            enable_high_event: self.enable_high_event,
+            ..InterruptConfig::default()
+        }
+        .int_interrupt_cfg()
     }
 }
 
+/// Window captured around the pressure event, see `poll_capture()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capture {
+    /// Number of samples read into the buffer, the oldest first
+    pub len: usize,
+    /// Samples were overwritten before the capture (FIFO overrun flag)
+    pub overrun: bool,
+    /// Low pressure event
+    pub low_event: bool,
+    /// High pressure event
+    pub high_event: bool,
+}
 
-#[cfg(feature = "lps22hb")] 
-impl FIFOConfig {
-    /// Returns values to be written to CTRL_REG2 and FIFO_CTRL:
-    fn f_ctrl_reg2(&self) -> u8 {
-        let mut data = 0u8;
-        // THIS RESULT MUST THEN BE COMBINED WITH THE OTHER BIT SETTINGS
-        if self.enable_watermark.status() {
-            data |= 1 << 5;
+/// FIFO setup the driver keeps track of, so that the functions depending on it don't read it back
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum FifoUse {
+    /// Set up by `configure_fifo()`, or unknown (e.g. after `new()`)
+    Other,
+    /// Trigger capture armed by `arm_capture()`, with the FIFO mode switching on the event
+    Capture(FIFO_TRIGGER),
+    /// High-resolution mode set by `high_resolution_mode()` (LPS25HB)
+    #[cfg(feature = "lps25hb")]
+    HighResolution,
+}
+
+impl FifoUse {
+    /// FIFO setup after a change of the output data rate, which leaves the high-resolution mode
+    pub(crate) fn odr_changed(self) -> Self {
+        match self {
+            #[cfg(feature = "lps25hb")]
+            FifoUse::HighResolution => FifoUse::Other,
+            fifo_use => fifo_use,
         }
-        data
-    }
-    fn f_fifo_ctrl(&self) -> u8 {
-        let mut data = 0u8;
-        data |= self.fifo_mode.value();
-        data |= self.watermark_level;
-        data
     }
 }
 
-
+impl Capture {
+    pub(crate) fn new(len: usize, fifo_status: &FifoStatus, int_status: &IntStatus) -> Self {
+        Capture {
+            len,
+            overrun: fifo_status.fifo_overrun,
+            low_event: int_status.diff_press_low,
+            high_event: int_status.diff_press_high,
+        }
+    }
+}
 
 #[derive(Debug)]
 /// Contents of the FIFO_STATUS register (threshold reached, overrun, empty, stored data level)
@@ -95,94 +157,793 @@ pub struct FifoStatus {
     pub fifo_empty: bool,
     pub fifo_level: u8,
 }
- 
-impl<T, E> LPS2X<T>
- where
-     T: Interface<Error = E>,
- {
-    // The FIFO buffer is enabled when the FIFO_EN bit in CTRL_REG2 (11h) is set to '1'
-    // and each mode is selected by the FIFO_MODE[2:0] bits in FIFO_CTRL (14h).
-
-     /// Enable and configure FIFO
-    pub fn configure_fifo(&mut self, flag: FIFO_ON, config: FIFOConfig) -> Result<(), T::Error> {
-         match flag {
-             FIFO_ON::Enabled => self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::FIFO_EN),
-             FIFO_ON::Disabled => self.clear_register_bit_flag(Registers::CTRL_REG2, Bitmasks::FIFO_EN),
-         }?;
- 
-
-        let mut reg_data = [0u8];
-        self.interface.read(Registers::CTRL_REG2.addr(), &mut reg_data)?;
-        
-        reg_data[0] |= config.f_ctrl_reg2();
-        self.interface.write(Registers::CTRL_REG2.addr(), reg_data[0])?;
-        
-        self.interface.write(Registers::FIFO_CTRL.addr(), config.f_fifo_ctrl())?;
- 
-        Ok(())
-    }
- 
- 
-    /// Get flags and FIFO level from the FIFO_STATUS register
-    pub fn get_fifo_status(&mut self) -> Result<FifoStatus, T::Error> {
-         
-        let reg_value = self.read_register(Registers::FIFO_STATUS)?;
-        let fifo_level_value = self.read_fifo_level()?;
- 
-        let status = FifoStatus {
-            /// Is FIFO filling equal or higher than the threshold?
-            fifo_thresh_reached: match reg_value & Bitmasks::FTH_FIFO {
-                0 => false,
-                _ => true,
-            },
-            /// Is FIFO full and at least one sample has been overwritten?
-            fifo_overrun: match reg_value & Bitmasks::OVR {
-                0 => false,
-                _ => true,
-            },
-            
-            #[cfg(feature="lps25hb")]
-            /// Is FIFO empty?
-            fifo_empty: match reg_value & Bitmasks::EMPTY_FIFO {
-                0 => false,
-                _ => true,
+
+impl FifoStatus {
+    /// Decode the content of the FIFO_STATUS register(s), the flags are in the last one
+    pub(crate) fn from_registers<C: Variant>(data: &[u8]) -> Self {
+        let reg_value = data[data.len() - 1];
+        let fifo_level_value = C::fifo_level(data);
+        FifoStatus {
+            // Is FIFO filling equal or higher than the threshold?
+            fifo_thresh_reached: reg_value & C::FTH_FIFO != 0,
+            // Is FIFO full and at least one sample has been overwritten?
+            fifo_overrun: reg_value & C::OVR != 0,
+            // Is FIFO empty?
+            fifo_empty: fifo_level_value == 0,
+            // Read FIFO stored data level
+            fifo_level: fifo_level_value,
+        }
+    }
+}
+
+/// Measurement read from the FIFO, with its timestamp
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedMeasurement {
+    /// Time of the sample in microseconds, in the time base of the drain time given to `fifo_samples()`
+    pub timestamp_us: u64,
+    pub measurement: Measurement,
+}
+
+/// Item returned when reading the FIFO with `fifo_samples()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FifoItem {
+    /// The FIFO overrun flag was set: samples were overwritten and lost before the following ones
+    Gap,
+    /// Sample, the oldest first
+    Sample(TimedMeasurement),
+}
+
+/// Position and timing of the samples being read from the FIFO
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FifoCursor {
+    /// Gap marker not returned yet
+    gap: bool,
+    /// Number of samples stored when the FIFO status was read
+    level: u8,
+    /// Number of samples read so far
+    read: u8,
+    period_us: u32,
+    drain_time_us: u64,
+    sensitivity: u16,
+    status: DataStatus,
+    /// Current temperature, for the models storing only pressure in the FIFO
+    temperature: [u8; 2],
+}
+
+impl FifoCursor {
+    /// The newest sample, the last one stored, is assumed to be taken at the drain time,
+    /// the other ones one output data rate period apart.
+    pub(crate) fn new(fifo_status: &FifoStatus, period_us: u32, drain_time_us: u64, sensitivity: u16, temperature: [u8; 2]) -> Self {
+        FifoCursor {
+            gap: fifo_status.fifo_overrun,
+            level: fifo_status.fifo_level,
+            read: 0,
+            period_us,
+            drain_time_us,
+            sensitivity,
+            status: DataStatus {
+                press_available: true,
+                temp_available: true,
+                press_overrun: fifo_status.fifo_overrun,
+                temp_overrun: fifo_status.fifo_overrun,
             },
+            temperature,
+        }
+    }
 
-            #[cfg(feature="lps22hb")]
-            /// Is FIFO empty?
-            fifo_empty: match fifo_level_value {
-                0 => true,
-                _ => false,
+    /// Return the gap marker once, if the FIFO overrun flag was set
+    pub(crate) fn take_gap(&mut self) -> bool {
+        core::mem::replace(&mut self.gap, false)
+    }
+
+    /// Are there samples left to read?
+    pub(crate) fn remaining(&self) -> bool {
+        self.read < self.level
+    }
+
+    /// Stop after an error
+    pub(crate) fn stop(&mut self) {
+        self.gap = false;
+        self.read = self.level;
+    }
+
+    /// Decode the next sample read from the FIFO output registers
+    pub(crate) fn decode<C: Variant>(&mut self, data: &[u8]) -> TimedMeasurement {
+        let temperature = match C::FIFO_DATA_LEN {
+            5 => [data[3], data[4]],
+            _ => self.temperature,
+        };
+        let age = (self.level - 1 - self.read) as u64 * self.period_us as u64;
+        self.read += 1;
+        TimedMeasurement {
+            timestamp_us: self.drain_time_us.saturating_sub(age),
+            measurement: Measurement {
+                pressure: pressure_from_bytes([data[0], data[1], data[2]], self.sensitivity),
+                temperature: temperature_from_bytes::<C>(temperature),
+                status: self.status,
             },
+        }
+    }
+}
 
-            /// Read FIFO stored data level
-            fifo_level: fifo_level_value,
+/// Iterator over the samples stored in the FIFO, see `fifo_samples()`
+pub struct FifoSamples<'a, T, C, MODE> {
+    sensor: &'a mut LPS2X<T, C, MODE>,
+    cursor: FifoCursor,
+}
+
+impl<'a, T, C, MODE, E> Iterator for FifoSamples<'a, T, C, MODE>
+where
+    T: Interface<Error = E>,
+    C: Variant,
+    MODE: PowerMode,
+{
+    type Item = Result<FifoItem, Error<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor.take_gap() {
+            return Some(Ok(FifoItem::Gap));
+        }
+        if !self.cursor.remaining() {
+            return None;
+        }
+        match self.sensor.read_fifo_sample(&mut self.cursor) {
+            Ok(sample) => Some(Ok(FifoItem::Sample(sample))),
+            Err(error) => {
+                self.cursor.stop();
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Samples stored in the FIFO, see `LPS2XAsync::fifo_samples()`
+#[cfg(feature = "async")]
+pub struct FifoSamplesAsync<'a, T, C, MODE, P> {
+    sensor: &'a mut LPS2XAsync<T, C, MODE, P>,
+    cursor: FifoCursor,
+}
+
+#[cfg(feature = "async")]
+impl<'a, T, C, MODE, P, E> FifoSamplesAsync<'a, T, C, MODE, P>
+where
+    T: AsyncInterface<Error = E>,
+    C: Variant,
+    MODE: PowerMode,
+{
+    /// Next item, the gap marker or a sample read from the FIFO, `None` after the last sample
+    pub async fn next(&mut self) -> Option<Result<FifoItem, Error<E>>> {
+        if self.cursor.take_gap() {
+            return Some(Ok(FifoItem::Gap));
+        }
+        if !self.cursor.remaining() {
+            return None;
+        }
+        match self.sensor.read_fifo_sample(&mut self.cursor).await {
+            Ok(sample) => Some(Ok(FifoItem::Sample(sample))),
+            Err(error) => {
+                self.cursor.stop();
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+macro_rules! impl_fifo {
+    ($driver:ident $(<$pin:ident>)?, $interface:ident, $delay:ident, $samples:ident; $($async:ident)?; $($await:ident)?) => {
+        impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+         where
+             T: $interface<Error = E>,
+             C: Variant,
+             MODE: PowerMode,
+         {
+            // The FIFO buffer is enabled when the FIFO_EN bit in CTRL_REG2 is set to '1'
+            // and each mode is selected by the FIFO_MODE[2:0] bits in FIFO_CTRL.
+            // LPS22HH has no FIFO_EN bit, the FIFO is disabled by selecting Bypass mode.
+
+             /// Enable and configure FIFO.
+             /// Returns `Error::OutOfRange` if the watermark level exceeds the FIFO depth,
+             /// `Error::InvalidConfig` if the settings can't be used together.
+            pub $($async)? fn configure_fifo(&mut self, flag: FIFO_ON, config: FIFOConfig<C>) -> Result<(), Error<E>> {
+                 if config.watermark_level >= C::FIFO_DEPTH {
+                     return Err(Error::OutOfRange);
+                 }
+                 let (ctrl_reg2, fifo_ctrl, fifo_wtm) = C::fifo_registers(flag, &config).ok_or(Error::InvalidConfig)?;
+                self.fifo_use = FifoUse::Other;
+
+                // the FIFO bits of CTRL_REG2 are all replaced, so that no setting of a previous configuration is left
+                let mut reg_data = self.read_register(C::CTRL_REG2) $(.$await)? ?;
+                reg_data &= !C::CTRL_REG2_FIFO_MASK;
+                reg_data |= ctrl_reg2;
+                if flag.status() {
+                    reg_data |= C::FIFO_EN;
+                }
+                self.write_register(C::CTRL_REG2, reg_data) $(.$await)? ?;
+
+                self.write_register(C::FIFO_CTRL, fifo_ctrl) $(.$await)? ?;
+                if let Some(address) = C::FIFO_WTM {
+                    self.write_register(address, fifo_wtm) $(.$await)? ?;
+                }
+
+                Ok(())
+            }
+
+
+            /// Get flags and FIFO level from the FIFO_STATUS register(s)
+            pub $($async)? fn get_fifo_status(&mut self) -> Result<FifoStatus, Error<E>> {
+
+                let mut data = [0u8; 2];
+                let data = &mut data[..C::FIFO_STATUS_LEN];
+                self.read_registers(C::FIFO_STATUS, data) $(.$await)? ?;
+                Ok(FifoStatus::from_registers::<C>(data))
+            }
+
+            /// Iterate over the samples stored in the FIFO, the oldest first, each one read in one transaction
+            /// (see `read_fifo()`), with timestamps: the newest sample is taken at `drain_time_us`
+            /// (the time the FIFO is drained, e.g. when the watermark interrupt is received, in microseconds
+            /// in any time base), the other ones one period of the output data rate apart.
+            /// The output data rate is read from CTRL_REG1.
+            /// A `FifoItem::Gap` is returned first if the FIFO overrun flag is set.
+            /// Returns `Error::InvalidConfig` if the sensor is in one-shot mode (or CTRL_REG1 holds a reserved value).
+            pub $($async)? fn fifo_samples(&mut self, drain_time_us: u64) -> Result<$samples<'_, T, C, MODE $(, $pin)?>, Error<E>> {
+                let ctrl_reg1 = self.read_register(C::CTRL_REG1) $(.$await)? ?;
+                let period_us = C::odr_from_register(ctrl_reg1).and_then(C::odr_period_us).ok_or(Error::InvalidConfig)?;
+                let cursor = self.fifo_cursor(period_us, drain_time_us) $(.$await)? ?;
+                Ok($samples { sensor: self, cursor })
+            }
+
+            /// Read the FIFO status, and the current temperature on the models storing only pressure in the FIFO
+            $($async)? fn fifo_cursor(&mut self, period_us: u32, drain_time_us: u64) -> Result<FifoCursor, Error<E>> {
+                let fifo_status = self.get_fifo_status() $(.$await)? ?;
+                let mut temperature = [0u8; 2];
+                if C::FIFO_DATA_LEN < 5 {
+                    self.read_registers(C::TEMP_OUT_L, &mut temperature) $(.$await)? ?;
+                }
+                Ok(FifoCursor::new(&fifo_status, period_us, drain_time_us, self.press_sensitivity, temperature))
+            }
+
+            /// Read the next sample from the FIFO output registers, in one transaction
+            $($async)? fn read_fifo_sample(&mut self, cursor: &mut FifoCursor) -> Result<TimedMeasurement, Error<E>> {
+                let mut data = [0u8; 5];
+                self.read_registers(C::FIFO_DATA, &mut data[..C::FIFO_DATA_LEN]) $(.$await)? ?;
+                Ok(cursor.decode::<C>(&data))
+            }
+
+            /// Read the samples stored in the FIFO, at most as many as `buffer` can hold, and return how many were read.
+            /// The FIFO level is read from FIFO_STATUS, then each sample is read in one transaction, which pops it from the FIFO.
+            /// On the models storing only pressure in the FIFO (LPS22DF, LPS25HB, LPS28DFW) the temperature
+            /// is the current one, read once for all the samples.
+            /// The data available flags of the samples are set, the overrun flags are set if the FIFO overrun flag was set.
+            pub $($async)? fn read_fifo(&mut self, buffer: &mut [Measurement]) -> Result<usize, Error<E>> {
+                // the samples are decoded as by `fifo_samples()`, without timestamps
+                let mut cursor = self.fifo_cursor(0, 0) $(.$await)? ?;
+                let mut count = 0;
+                for sample in buffer.iter_mut() {
+                    if !cursor.remaining() {
+                        break;
+                    }
+                    *sample = self.read_fifo_sample(&mut cursor) $(.$await)? ?.measurement;
+                    count += 1;
+                }
+                Ok(count)
+            }
+
+            /// Arm a trigger capture: set the pressure threshold, enable the latched pressure interrupts,
+            /// clear the FIFO and any pending event, and select the FIFO mode switching on the event.
+            /// The interrupt pin is not configured, see `configure_interrupts()`.
+            /// Returns `Error::InvalidConfig` if neither the low nor the high pressure event is enabled,
+            /// `Error::OutOfRange` if the threshold doesn't fit in the THS_P registers.
+            pub $($async)? fn arm_capture(&mut self, config: CaptureConfig) -> Result<(), Error<E>> {
+                if !config.enable_low_event.status() && !config.enable_high_event.status() {
+                    return Err(Error::InvalidConfig);
+                }
+                self.set_threshold(config.threshold) $(.$await)? ?;
+                // Bypass mode clears the FIFO
+                self.configure_fifo(FIFO_ON::Enabled, FIFOConfig::default()) $(.$await)? ?;
+
+                self.set_register_bit_flag(C::DIFF_EN_REG, C::DIFF_EN) $(.$await)? ?;
+                let mut interrupt_cfg = self.read_register(C::INTERRUPT_CFG) $(.$await)? ?;
+                interrupt_cfg &= !INTERRUPT_EVENTS_MASK;
+                interrupt_cfg |= config.interrupt_cfg();
+                self.write_register(C::INTERRUPT_CFG, interrupt_cfg) $(.$await)? ?;
+                self.get_int_status() $(.$await)? ?;
+
+                let fifo_config = FIFOConfig {
+                    fifo_mode: C::trigger_fifo_mode(config.trigger_mode),
+                    ..FIFOConfig::default()
+                };
+                self.configure_fifo(FIFO_ON::Enabled, fifo_config) $(.$await)? ?;
+                self.fifo_use = FifoUse::Capture(config.trigger_mode);
+                Ok(())
+            }
+
+            /// Check whether the capture armed with `arm_capture()` is complete, i.e. the FIFO is full after the event,
+            /// and if so read the window into `buffer` (which should hold `C::FIFO_DEPTH` samples, see `read_fifo()`).
+            /// Returns `None` while the capture is not complete, `Error::InvalidConfig` if no capture is armed
+            /// (or the FIFO was configured since).
+            ///
+            /// `event` tells whether the pressure event was signalled on the INT_DRDY pin since the capture was armed
+            /// (`data_signal_config` of `configure_interrupts()`). In Stream-to-FIFO mode the FIFO is also full before
+            /// the event, so it is not read until `event` is set: the samples before the event stay in the FIFO.
+            /// In Bypass-to-stream and Bypass-to-FIFO modes the FIFO stays empty until the event, a full FIFO
+            /// holds the window and `event` is not needed.
+            ///
+            /// The FIFO is drained before INT_SOURCE is read: reading INT_SOURCE releases the latched event, which rearms
+            /// the trigger and switches the FIFO back to its mode before the event (Bypass mode clears it,
+            /// Stream mode overwrites the window).
+            pub $($async)? fn poll_capture(&mut self, event: bool, buffer: &mut [Measurement]) -> Result<Option<Capture>, Error<E>> {
+                let trigger = match self.fifo_use {
+                    FifoUse::Capture(trigger) => trigger,
+                    _ => return Err(Error::InvalidConfig),
+                };
+                if matches!(trigger, FIFO_TRIGGER::Stream_to_FIFO) && !event {
+                    return Ok(None);
+                }
+                let fifo_status = self.get_fifo_status() $(.$await)? ?;
+                if fifo_status.fifo_level < C::FIFO_DEPTH {
+                    return Ok(None);
+                }
+                let len = self.read_fifo(buffer) $(.$await)? ?;
+                let int_status = self.get_int_status() $(.$await)? ?;
+                Ok(Some(Capture::new(len, &fifo_status, &int_status)))
+            }
+         }
+
+        #[cfg(feature = "lps25hb")]
+        impl<T, MODE, $($pin,)? E> $driver<T, LPS25HB, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            MODE: PowerMode,
+        {
+            /// Enter the high-resolution mode (LPS25HB only): FIFO Mean mode with a moving average of `samples`
+            /// pressure samples, decimated to 1 Hz, read with `poll_high_resolution()`.
+            /// The settings are applied together, as the datasheet requires:
+            /// - RES_CONF: internal averages AVGT 16 and AVGP 32 (AVGP 512 can't be used at 25 Hz)
+            /// - FIFO cleared (Bypass mode), then FIFO Mean mode with decimation to 1 Hz
+            /// - output data rate `samples.high_resolution_odr()`: the moving average covers at least
+            ///   the 1 Hz output period (1 Hz for 2 and 4 samples, 7 Hz for 8, 12.5 Hz for 16, 25 Hz for 32)
+            ///
+            /// Changing the output data rate or the FIFO settings afterwards leaves the high-resolution mode.
+            /// Returns the driver with the error if the settings can't be written.
+            pub $($async)? fn high_resolution_mode(mut self, samples: FIFO_MEAN) -> Result<$driver<T, LPS25HB, Continuous $(, $pin)?>, (Self, Error<E>)> {
+                match self.write_high_resolution(samples) $(.$await)? {
+                    Ok(()) => Ok(self.into_mode()),
+                    Err(error) => Err((self, error)),
+                }
+            }
+
+            /// Write the settings of the high-resolution mode, see `high_resolution_mode()`
+            $($async)? fn write_high_resolution(&mut self, samples: FIFO_MEAN) -> Result<(), Error<E>> {
+                // RES_CONF must be changed in power-down mode
+                self.clear_register_bit_flag(lps25hb::Registers::CTRL_REG1.addr(), lps25hb::Bitmasks::ODR_MASK) $(.$await)? ?;
+                let mut res_conf = self.read_register(lps25hb::Registers::RES_CONF.addr()) $(.$await)? ?;
+                res_conf &= !(lps25hb::Bitmasks::AVGT_MASK | lps25hb::Bitmasks::AVGP_MASK);
+                res_conf |= lps25hb::HIGH_RESOLUTION_RES_CONF;
+                self.write_register(lps25hb::Registers::RES_CONF.addr(), res_conf) $(.$await)? ?;
+
+                // Bypass mode clears the FIFO
+                self.configure_fifo(FIFO_ON::Enabled, FIFOConfig::default()) $(.$await)? ?;
+                let fifo_config = FIFOConfig {
+                    fifo_mode: lps25hb::FIFO_MODE::FIFO_Mean,
+                    enable_decimating: FLAG::Enabled,
+                    fifo_mean_config: samples,
+                    ..FIFOConfig::default()
+                };
+                self.configure_fifo(FIFO_ON::Enabled, fifo_config) $(.$await)? ?;
+
+                let mut ctrl_reg1 = self.read_register(lps25hb::Registers::CTRL_REG1.addr()) $(.$await)? ?;
+                ctrl_reg1 &= !lps25hb::Bitmasks::ODR_MASK;
+                ctrl_reg1 |= samples.high_resolution_odr().value() | lps25hb::Bitmasks::PD;
+                self.write_register(lps25hb::Registers::CTRL_REG1.addr(), ctrl_reg1) $(.$await)? ?;
+                self.fifo_use = FifoUse::HighResolution;
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "lps25hb")]
+        impl<T, $($pin,)? E> $driver<T, LPS25HB, Continuous $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+        {
+            /// Read the averaged output of the high-resolution mode (LPS25HB only), see `high_resolution_mode()`.
+            /// Returns `nb::Error::WouldBlock` until a new averaged pressure sample is available (once per second),
+            /// `Error::InvalidConfig` if the driver left the high-resolution mode since (e.g. `set_datarate()`,
+            /// `configure_fifo()`, `software_reset()`). The driver keeps track of the mode, so that only the output
+            /// registers are read; settings written outside the driver are not detected.
+            pub $($async)? fn poll_high_resolution(&mut self) -> nb::Result<Measurement, Error<E>> {
+                if !matches!(self.fifo_use, FifoUse::HighResolution) {
+                    return Err(nb::Error::Other(Error::InvalidConfig));
+                }
+                let measurement = self.read_measurement() $(.$await)? ?;
+                if !measurement.status.press_available {
+                    return Err(nb::Error::WouldBlock);
+                }
+                Ok(measurement)
+            }
+
+            /// Wait for the next averaged output of the high-resolution mode, polling the STATUS register
+            /// every millisecond (see `poll_high_resolution()`).
+            /// Returns `Error::InvalidConfig` if the driver left the high-resolution mode,
+            /// `Error::Timeout` if no sample is available after `timeout_ms` milliseconds.
+            pub $($async)? fn read_high_resolution<D: $delay>(&mut self, delay: &mut D, timeout_ms: u16) -> Result<Measurement, Error<E>> {
+                if !matches!(self.fifo_use, FifoUse::HighResolution) {
+                    return Err(Error::InvalidConfig);
+                }
+                let mut elapsed: u16 = 0;
+                while !self.is_register_bit_flag_high(lps25hb::Registers::STATUS.addr(), lps25hb::Bitmasks::P_DA) $(.$await)? ? {
+                    if elapsed >= timeout_ms {
+                        return Err(Error::Timeout);
+                    }
+                    delay.delay_ms(1) $(.$await)?;
+                    elapsed += 1;
+                }
+                self.read_measurement() $(.$await)?
+            }
+        }
+    };
+}
+
+impl_fifo!(LPS2X, Interface, DelayNs, FifoSamples; ;);
+#[cfg(feature = "async")]
+impl_fifo!(LPS2XAsync<P>, AsyncInterface, AsyncDelayNs, FifoSamplesAsync; async; await);
+
+#[cfg(all(test, feature = "lps25hb"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use mock::RegisterFile;
+    use variant::lps25hb::Bitmasks;
+
+    const EMPTY: Measurement = Measurement {
+        pressure: Pressure::from_centipascal(0),
+        temperature: Temperature::from_centidegrees(0),
+        status: DataStatus {
+            press_available: false,
+            temp_available: false,
+            press_overrun: false,
+            temp_overrun: false,
+        },
+    };
+
+    /// 1000 hPa, 1001 hPa, 1002 hPa
+    const PRESSURES: [[u8; 3]; 3] = [[0x00, 0x80, 0x3E], [0x00, 0x90, 0x3E], [0x00, 0xA0, 0x3E]];
+
+    /// LPS25HB FIFO_STATUS: FSS is the number of samples minus one
+    fn fifo_status(level: u8, overrun: bool) -> u8 {
+        let mut value = match level {
+            0 => Bitmasks::EMPTY_FIFO,
+            _ => level - 1,
         };
-        Ok(status)
+        if overrun {
+            value |= Bitmasks::OVR;
+        }
+        value
     }
-    
-    #[cfg(feature = "lps22hb")]
-    /// Read FIFO stored data level
-    // pub fn read_fifo_level(&mut self) -> Result<u8, T::Error> {
-    fn read_fifo_level(&mut self) -> Result<u8, T::Error> {
-        let mut data = [0u8; 1];
-        self.interface.read(Registers::FIFO_STATUS.addr(), &mut data)?;
-        let level = data[0] & Bitmasks::FSS_MASK;
-        Ok(level)
-    }
-    
-    #[cfg(feature = "lps25hb")]
-    /// Read FIFO stored data level
-    //pub fn read_fifo_level(&mut self) -> Result<u8, T::Error> {
-    fn read_fifo_level(&mut self) -> Result<u8, T::Error> {
-        let mut reg_data = [0u8];
-        self.interface.read(Registers::FIFO_STATUS.addr(), &mut reg_data)?;
-    
-        let level: u8 = match self.is_register_bit_flag_high(Registers::FIFO_STATUS, Bitmasks::EMPTY_FIFO)? {
-            true => 0,
-            false => (reg_data[0] & Bitmasks::FSS_MASK) + 1,
+
+    /// LPS25HB storing `level` samples, at 42.5 °C
+    fn sensor(level: u8, overrun: bool) -> LPS2X<RegisterFile, LPS25HB> {
+        let mut registers = RegisterFile::new();
+        registers.registers[LPS25HB::FIFO_STATUS as usize] = fifo_status(level, overrun);
+        let samples: [&[u8]; 3] = [&PRESSURES[0], &PRESSURES[1], &PRESSURES[2]];
+        registers.fill_fifo(LPS25HB::FIFO_DATA, &samples[..level as usize]);
+        registers.driver(LPS25HB)
+    }
+
+    #[test]
+    fn read_fifo_drains_the_samples_with_the_current_temperature() {
+        let mut sensor = sensor(3, false);
+        let mut buffer = [EMPTY; 4];
+        assert_eq!(sensor.read_fifo(&mut buffer).unwrap(), 3);
+
+        for (sample, hpa) in buffer[..3].iter().zip(1000..) {
+            assert_eq!(sample.pressure, Pressure::from_hectopascal(hpa));
+            assert_eq!(sample.temperature.as_centidegrees(), 4250);
+            assert!(sample.status.press_available && sample.status.temp_available);
+            assert!(!sample.status.press_overrun && !sample.status.temp_overrun);
+        }
+        assert_eq!(buffer[3], EMPTY);
+        // FIFO level, temperature once, then one transaction per sample
+        let fifo_data = (LPS25HB::FIFO_DATA, 3);
+        assert_eq!(
+            sensor.interface.reads,
+            [(LPS25HB::FIFO_STATUS, 1), (LPS25HB::TEMP_OUT_L, 2), fifo_data, fifo_data, fifo_data]
+        );
+        assert!(sensor.interface.fifo.is_empty());
+    }
+
+    #[test]
+    fn read_fifo_stops_when_the_buffer_is_full() {
+        let mut sensor = sensor(3, false);
+        let mut buffer = [EMPTY; 2];
+        assert_eq!(sensor.read_fifo(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer[1].pressure, Pressure::from_hectopascal(1001));
+        // the newest sample is left in the FIFO
+        assert_eq!(sensor.interface.fifo.len(), 1);
+    }
+
+    #[test]
+    fn read_fifo_reports_an_overrun_in_the_sample_status() {
+        let mut sensor = sensor(3, true);
+        let mut buffer = [EMPTY; 3];
+        assert_eq!(sensor.read_fifo(&mut buffer).unwrap(), 3);
+        assert!(buffer.iter().all(|sample| sample.status.press_overrun && sample.status.temp_overrun));
+    }
+
+    #[test]
+    fn read_fifo_reads_nothing_from_an_empty_fifo() {
+        let mut sensor = sensor(0, false);
+        let mut buffer = [EMPTY; 3];
+        assert_eq!(sensor.read_fifo(&mut buffer).unwrap(), 0);
+        assert!(!sensor.interface.reads.contains(&(LPS25HB::FIFO_DATA, 3)));
+    }
+
+    /// LPS25HB storing `level` samples, in continuous mode at 25 Hz
+    fn sensor_at_25hz(level: u8, overrun: bool) -> LPS2X<RegisterFile, LPS25HB> {
+        let mut sensor = sensor(level, overrun);
+        sensor.interface.registers[LPS25HB::CTRL_REG1 as usize] = lps25hb::ODR::_25Hz.value() | Bitmasks::PD;
+        sensor
+    }
+
+    fn timestamps(items: &[FifoItem]) -> std::vec::Vec<Option<u64>> {
+        items
+            .iter()
+            .map(|item| match item {
+                FifoItem::Gap => None,
+                FifoItem::Sample(sample) => Some(sample.timestamp_us),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fifo_samples_are_timestamped_from_the_output_data_rate() {
+        let mut sensor = sensor_at_25hz(3, false);
+        let items: std::vec::Vec<_> = sensor.fifo_samples(1_000_000).unwrap().map(Result::unwrap).collect();
+
+        // the newest sample at the drain time, 40 ms apart
+        assert_eq!(timestamps(&items), [Some(920_000), Some(960_000), Some(1_000_000)]);
+        if let FifoItem::Sample(sample) = items[0] {
+            assert_eq!(sample.measurement.pressure, Pressure::from_hectopascal(1000));
+            assert_eq!(sample.measurement.temperature.as_centidegrees(), 4250);
+        }
+        assert_eq!(sensor.interface.reads[0], (LPS25HB::CTRL_REG1, 1));
+    }
+
+    #[test]
+    fn fifo_samples_report_an_overrun_as_a_gap_before_the_samples() {
+        let mut sensor = sensor_at_25hz(3, true);
+        let items: std::vec::Vec<_> = sensor.fifo_samples(1_000_000).unwrap().map(Result::unwrap).collect();
+
+        assert_eq!(timestamps(&items), [None, Some(920_000), Some(960_000), Some(1_000_000)]);
+        assert!(items[1..].iter().all(|item| matches!(item, FifoItem::Sample(sample) if sample.measurement.status.press_overrun)));
+    }
+
+    #[test]
+    fn fifo_samples_timestamps_saturate_at_zero() {
+        let mut sensor = sensor_at_25hz(3, false);
+        let items: std::vec::Vec<_> = sensor.fifo_samples(50_000).unwrap().map(Result::unwrap).collect();
+        assert_eq!(timestamps(&items), [Some(0), Some(10_000), Some(50_000)]);
+    }
+
+    #[test]
+    fn fifo_samples_stop_after_a_bus_error() {
+        let mut sensor = sensor_at_25hz(3, true);
+        // CTRL_REG1, FIFO_STATUS, TEMP_OUT and the first sample
+        sensor.interface.fail_after = Some(4);
+        let mut samples = sensor.fifo_samples(1_000_000).unwrap();
+
+        assert!(matches!(samples.next(), Some(Ok(FifoItem::Gap))));
+        assert!(matches!(samples.next(), Some(Ok(FifoItem::Sample(_)))));
+        assert!(matches!(samples.next(), Some(Err(Error::Interface(_)))));
+        assert!(samples.next().is_none());
+    }
+
+    #[test]
+    fn fifo_samples_need_a_continuous_output_data_rate() {
+        // one-shot mode
+        let mut sensor = sensor(3, false);
+        assert!(matches!(sensor.fifo_samples(1_000_000), Err(Error::InvalidConfig)));
+        // reserved ODR value
+        sensor.interface.registers[LPS25HB::CTRL_REG1 as usize] = 0b0111_0000 | Bitmasks::PD;
+        assert!(matches!(sensor.fifo_samples(1_000_000), Err(Error::InvalidConfig)));
+    }
+
+    #[test]
+    fn configure_fifo_replaces_the_fifo_bits_of_ctrl_reg2() {
+        let mut sensor = sensor(0, false);
+        sensor.interface.registers[LPS25HB::CTRL_REG2 as usize] = Bitmasks::AUTOZERO;
+        let config = FIFOConfig {
+            enable_watermark: FLAG::Enabled,
+            fifo_mode: lps25hb::FIFO_MODE::FIFO,
+            watermark_level: 16,
+            ..FIFOConfig::default()
         };
-        Ok(level)
+        sensor.configure_fifo(FIFO_ON::Enabled, config).unwrap();
+        assert_eq!(sensor.interface.registers[LPS25HB::CTRL_REG2 as usize], Bitmasks::AUTOZERO | Bitmasks::FIFO_EN | Bitmasks::STOP_ON_FTH);
+
+        sensor.configure_fifo(FIFO_ON::Disabled, FIFOConfig::default()).unwrap();
+        assert_eq!(sensor.interface.registers[LPS25HB::CTRL_REG2 as usize], Bitmasks::AUTOZERO);
+    }
+
+    #[test]
+    fn arm_capture_clears_a_previous_watermark_and_decimation_config() {
+        let mut sensor = sensor(0, false);
+        let config = FIFOConfig {
+            enable_watermark: FLAG::Enabled,
+            fifo_mode: lps25hb::FIFO_MODE::FIFO_Mean,
+            enable_decimating: FLAG::Enabled,
+            ..FIFOConfig::default()
+        };
+        sensor.configure_fifo(FIFO_ON::Enabled, config).unwrap();
+        sensor.interface.registers[LPS25HB::INT_SOURCE as usize] = Bitmasks::IA | Bitmasks::PH;
+        sensor.interface.clear_on_read.push(LPS25HB::INT_SOURCE);
+
+        sensor.arm_capture(CaptureConfig::default()).unwrap();
+        let registers = &sensor.interface.registers;
+        assert_eq!(registers[LPS25HB::CTRL_REG2 as usize], Bitmasks::FIFO_EN);
+        assert_eq!(registers[LPS25HB::FIFO_CTRL as usize], lps25hb::FIFO_MODE::Stream_to_FIFO.value());
+        assert_eq!(registers[LPS25HB::INTERRUPT_CFG as usize], Bitmasks::LIR | Bitmasks::PH_E);
+        assert_eq!(registers[LPS25HB::CTRL_REG1 as usize] & Bitmasks::DIFF_EN, Bitmasks::DIFF_EN);
+        // the pending event is released, the FIFO cleared in Bypass mode before the trigger mode is selected
+        assert_eq!(registers[LPS25HB::INT_SOURCE as usize], 0);
+        assert_eq!(sensor.interface.written(LPS25HB::FIFO_CTRL)[1..], [lps25hb::FIFO_MODE::Bypass.value(), lps25hb::FIFO_MODE::Stream_to_FIFO.value()]);
     }
 
- }
+    /// LPS25HB with a full FIFO and INT_SOURCE holding `int_source` until it is read
+    fn full_sensor(int_source: u8) -> LPS2X<RegisterFile, LPS25HB> {
+        let mut registers = RegisterFile::new();
+        registers.registers[LPS25HB::FIFO_STATUS as usize] = fifo_status(LPS25HB::FIFO_DEPTH, true);
+        registers.fill_fifo(LPS25HB::FIFO_DATA, &[&PRESSURES[0][..]; LPS25HB::FIFO_DEPTH as usize]);
+        registers.registers[LPS25HB::INT_SOURCE as usize] = int_source;
+        registers.clear_on_read.push(LPS25HB::INT_SOURCE);
+        registers.driver(LPS25HB)
+    }
+
+    #[test]
+    fn poll_capture_waits_for_a_full_fifo() {
+        let mut sensor = sensor(3, false);
+        sensor.fifo_use = FifoUse::Capture(FIFO_TRIGGER::Bypass_to_FIFO);
+        let mut buffer = [EMPTY; LPS25HB::FIFO_DEPTH as usize];
+        assert_eq!(sensor.poll_capture(false, &mut buffer).unwrap(), None);
+        assert_eq!(sensor.interface.reads, [(LPS25HB::FIFO_STATUS, 1)]);
+    }
+
+    #[test]
+    fn poll_capture_drains_the_fifo_before_reading_int_source() {
+        for trigger in [FIFO_TRIGGER::Stream_to_FIFO, FIFO_TRIGGER::Bypass_to_stream, FIFO_TRIGGER::Bypass_to_FIFO] {
+            let mut sensor = full_sensor(Bitmasks::IA | Bitmasks::PH);
+            sensor.fifo_use = FifoUse::Capture(trigger);
+            let mut buffer = [EMPTY; LPS25HB::FIFO_DEPTH as usize];
+            let capture = sensor.poll_capture(true, &mut buffer).unwrap();
+
+            let expected = Capture { len: LPS25HB::FIFO_DEPTH as usize, overrun: true, low_event: false, high_event: true };
+            assert_eq!(capture, Some(expected));
+            assert!(buffer.iter().all(|sample| sample.pressure == Pressure::from_hectopascal(1000)));
+            assert!(sensor.interface.fifo.is_empty());
+            // INT_SOURCE is read once, after the window
+            let reads = &sensor.interface.reads;
+            assert_eq!(reads.iter().filter(|(address, _)| *address == LPS25HB::INT_SOURCE).count(), 1);
+            assert_eq!(reads.last(), Some(&(LPS25HB::INT_SOURCE, 1)));
+        }
+    }
+
+    #[test]
+    fn poll_capture_keeps_the_stream_window_until_the_event() {
+        let mut sensor = full_sensor(0);
+        sensor.arm_capture(CaptureConfig::default()).unwrap();
+        let armed_reads = sensor.interface.reads.len();
+        let mut buffer = [EMPTY; LPS25HB::FIFO_DEPTH as usize];
+        for _ in 0..3 {
+            assert_eq!(sensor.poll_capture(false, &mut buffer).unwrap(), None);
+        }
+        // the samples before the event are still in the FIFO, which is not even read
+        assert_eq!(sensor.interface.reads.len(), armed_reads);
+        assert_eq!(sensor.interface.fifo.len(), LPS25HB::FIFO_DEPTH as usize);
+
+        sensor.interface.registers[LPS25HB::INT_SOURCE as usize] = Bitmasks::IA | Bitmasks::PH;
+        let capture = sensor.poll_capture(true, &mut buffer).unwrap().unwrap();
+        assert_eq!(capture.len, LPS25HB::FIFO_DEPTH as usize);
+        assert!(capture.high_event);
+        assert!(buffer.iter().all(|sample| sample.pressure == Pressure::from_hectopascal(1000)));
+    }
+
+    #[test]
+    fn poll_capture_needs_an_armed_capture() {
+        let mut sensor = full_sensor(Bitmasks::IA | Bitmasks::PH);
+        let mut buffer = [EMPTY; LPS25HB::FIFO_DEPTH as usize];
+        assert!(matches!(sensor.poll_capture(true, &mut buffer), Err(Error::InvalidConfig)));
+
+        sensor.arm_capture(CaptureConfig::default()).unwrap();
+        sensor.configure_fifo(FIFO_ON::Enabled, FIFOConfig::default()).unwrap();
+        assert!(matches!(sensor.poll_capture(true, &mut buffer), Err(Error::InvalidConfig)));
+        assert_eq!(sensor.interface.fifo.len(), LPS25HB::FIFO_DEPTH as usize);
+    }
+
+    #[test]
+    fn high_resolution_mode_replaces_a_previous_fifo_config() {
+        let mut sensor = sensor(0, false);
+        let config = FIFOConfig {
+            enable_watermark: FLAG::Enabled,
+            fifo_mode: lps25hb::FIFO_MODE::Stream,
+            watermark_level: 16,
+            ..FIFOConfig::default()
+        };
+        sensor.configure_fifo(FIFO_ON::Enabled, config).unwrap();
+        sensor.interface.registers[LPS25HB::CTRL_REG1 as usize] = lps25hb::ODR::_25Hz.value() | Bitmasks::PD | Bitmasks::BDU;
+        // AVGT 64, AVGP 512
+        sensor.interface.registers[LPS25HB::RES_CONF as usize] = 0b0000_1111;
+        sensor.interface.writes.clear();
+
+        let sensor = sensor.high_resolution_mode(FIFO_MEAN::_16sample).map_err(|(_, error)| error).unwrap();
+        let registers = &sensor.interface.registers;
+        assert_eq!(registers[LPS25HB::RES_CONF as usize], lps25hb::HIGH_RESOLUTION_RES_CONF);
+        assert_eq!(registers[LPS25HB::CTRL_REG2 as usize], Bitmasks::FIFO_EN | Bitmasks::FIFO_MEAN_DEC);
+        assert_eq!(registers[LPS25HB::FIFO_CTRL as usize], lps25hb::FIFO_MODE::FIFO_Mean.value() | FIFO_MEAN::_16sample.value());
+        assert_eq!(registers[LPS25HB::CTRL_REG1 as usize], lps25hb::ODR::_12_5Hz.value() | Bitmasks::PD | Bitmasks::BDU);
+        // RES_CONF written in power-down mode, the FIFO cleared in Bypass mode
+        let writes = &sensor.interface.writes;
+        assert_eq!(writes[0], (LPS25HB::CTRL_REG1, Bitmasks::PD | Bitmasks::BDU));
+        assert_eq!(writes[1], (LPS25HB::RES_CONF, lps25hb::HIGH_RESOLUTION_RES_CONF));
+        assert_eq!(sensor.interface.written(LPS25HB::FIFO_CTRL)[0], lps25hb::FIFO_MODE::Bypass.value());
+    }
+
+    #[test]
+    fn high_resolution_mode_returns_the_driver_on_a_bus_error() {
+        let mut sensor = sensor(0, false);
+        // CTRL_REG1 updated, RES_CONF read
+        sensor.interface.fail_after = Some(3);
+        let (sensor, error) = sensor.high_resolution_mode(FIFO_MEAN::_32sample).err().unwrap();
+        assert!(matches!(error, Error::Interface(_)));
+        assert!(sensor.interface.written(LPS25HB::RES_CONF).is_empty());
+    }
+
+    #[test]
+    fn poll_high_resolution_reads_the_averaged_output() {
+        let sensor = sensor(0, false);
+        let mut sensor = sensor.high_resolution_mode(FIFO_MEAN::_32sample).map_err(|(_, error)| error).unwrap();
+        let status = <LPS25HB as Variant>::STATUS;
+        sensor.interface.registers[LPS25HB::PRESS_OUT_XL as usize..][..3].copy_from_slice(&PRESSURES[1]);
+        sensor.interface.script(status, &[0]);
+        sensor.interface.registers[status as usize] = Bitmasks::P_DA;
+
+        assert!(matches!(sensor.poll_high_resolution(), Err(nb::Error::WouldBlock)));
+        let measurement = sensor.poll_high_resolution().unwrap();
+        assert_eq!(measurement.pressure, Pressure::from_hectopascal(1001));
+
+        // another FIFO configuration leaves the high-resolution mode
+        sensor.configure_fifo(FIFO_ON::Enabled, FIFOConfig::default()).unwrap();
+        assert!(matches!(sensor.poll_high_resolution(), Err(nb::Error::Other(Error::InvalidConfig))));
+    }
+
+    #[test]
+    fn high_resolution_mode_is_left_by_a_data_rate_change() {
+        let sensor = sensor(0, false);
+        let mut sensor = sensor.high_resolution_mode(FIFO_MEAN::_8sample).map_err(|(_, error)| error).unwrap();
+        sensor.interface.reads.clear();
+        assert!(matches!(sensor.poll_high_resolution(), Err(nb::Error::WouldBlock)));
+        // the driver keeps track of the mode, only the output registers are read
+        assert_eq!(sensor.interface.reads, [(<LPS25HB as Variant>::STATUS, 6)]);
+
+        sensor.set_datarate(lps25hb::ODR::_25Hz).unwrap();
+        assert!(matches!(sensor.poll_high_resolution(), Err(nb::Error::Other(Error::InvalidConfig))));
+    }
+
+    #[cfg(feature = "lps22hb")]
+    #[test]
+    fn read_fifo_reads_the_temperature_of_each_sample() {
+        let mut registers = RegisterFile::new();
+        // FSS is the number of samples
+        registers.registers[<LPS22HB as Variant>::FIFO_STATUS as usize] = 2;
+        // 1000 hPa at 25 °C, 1001 hPa at 25.5 °C
+        registers.fill_fifo(LPS22HB::FIFO_DATA, &[&[0x00, 0x80, 0x3E, 0xC4, 0x09], &[0x00, 0x90, 0x3E, 0xF6, 0x09]]);
+        let mut sensor: LPS2X<_, _> = registers.driver(LPS22HB);
+
+        let mut buffer = [EMPTY; 3];
+        assert_eq!(sensor.read_fifo(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer[0].pressure, Pressure::from_hectopascal(1000));
+        assert_eq!(buffer[0].temperature.as_centidegrees(), 2500);
+        assert_eq!(buffer[1].pressure, Pressure::from_hectopascal(1001));
+        assert_eq!(buffer[1].temperature.as_centidegrees(), 2550);
+        assert!(!sensor.interface.reads.contains(&(LPS22HB::TEMP_OUT_L, 2)));
+    }
+}
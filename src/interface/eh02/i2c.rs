@@ -0,0 +1,45 @@
+//! I2C Interface (embedded-hal 0.2)
+use crate::interface::Interface;
+use crate::interface::i2c::MULTIBYTE;
+pub use crate::interface::i2c::{Error, I2cAddress};
+use embedded_hal_02::blocking::i2c::{Write, WriteRead};
+
+/// This holds `I2C` and device address
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    dev_addr: u8,    
+}
+
+impl<I2C> I2cInterface<I2C> {
+    /// Initializes an Interface with `I2C` instance and device address
+    /// # Arguments
+    /// * `i2c` - I2C instance
+    /// * `dev_addr` - `I2cAddress`: register address for the pressure sensor
+    pub fn init(i2c: I2C, dev_addr: I2cAddress) -> Self {
+        Self {
+            i2c,
+            dev_addr: dev_addr.addr(),            
+        }
+    }
+}
+
+// Implementation of `Interface`
+impl<I2C, CommE> Interface for I2cInterface<I2C>
+where
+    I2C: WriteRead<Error = CommE> + Write<Error = CommE>,
+{
+    type Error = Error<CommE>;
+    const MULTIBYTE: u8 = MULTIBYTE;
+
+    fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {        
+        self.i2c
+            .write(self.dev_addr, &[addr, value])
+            .map_err(Error::Comm)
+    }
+
+    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {    
+        self.i2c
+            .write_read(self.dev_addr, &[addr], buffer)
+            .map_err(Error::Comm)
+    }
+}
@@ -0,0 +1,7 @@
+//! Interfaces based on the `embedded-hal` 0.2 traits (requires the `embedded-hal-02` feature)
+//!
+//! Use these with HALs that don't implement `embedded-hal` 1.0 yet.
+pub mod spi;
+pub use self::spi::SpiInterface;
+pub mod i2c;
+pub use self::i2c::I2cInterface;
@@ -1,8 +1,11 @@
 use super::Interface;
-use embedded_hal::blocking::i2c::{Write, WriteRead};
- 
+#[cfg(feature = "async")]
+use super::AsyncInterface;
+use embedded_hal::i2c::I2c;
 
-const MULTIBYTE: u8 = 0b1000_0000;
+
+/// MSB of the register address. When 1, increments the address in multiple reads.
+pub(crate) const MULTIBYTE: u8 = 0b1000_0000;
 
 /// Errors in this crate
 #[derive(Debug)]
@@ -12,11 +15,11 @@ pub enum Error<CommE> {
 
 /// Pressure sensor address for I2C communication
 #[allow(non_camel_case_types)]
-pub enum I2cAddress {    
+pub enum I2cAddress {
     /// SA0 pad tied to VCC
     SA0_VCC =   0b1011101,
     /// SA0 pad tied to ground
-    SA0_GND =   0b1011100,   
+    SA0_GND =   0b1011100,
 }
 
 impl I2cAddress {
@@ -28,7 +31,7 @@ impl I2cAddress {
 /// This holds `I2C` and device address
 pub struct I2cInterface<I2C> {
     i2c: I2C,
-    dev_addr: u8,    
+    dev_addr: u8,
 }
 
 impl<I2C> I2cInterface<I2C> {
@@ -39,7 +42,7 @@ impl<I2C> I2cInterface<I2C> {
     pub fn init(i2c: I2C, dev_addr: I2cAddress) -> Self {
         Self {
             i2c,
-            dev_addr: dev_addr.addr(),            
+            dev_addr: dev_addr.addr(),
         }
     }
 }
@@ -47,36 +50,44 @@ impl<I2C> I2cInterface<I2C> {
 // Implementation of `Interface`
 impl<I2C, CommE> Interface for I2cInterface<I2C>
 where
-    I2C: WriteRead<Error = CommE> + Write<Error = CommE>,
+    I2C: I2c<Error = CommE>,
 {
     type Error = Error<CommE>;
+    const MULTIBYTE: u8 = MULTIBYTE;
 
+    fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        self.i2c
+            .write(self.dev_addr, &[addr, value])
+            .map_err(Error::Comm)
+    }
 
-    fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {        
-        core::prelude::v1::Ok(
-            self.i2c
-                //.write(sensor_addr, &[addr, value])
-                .write(self.dev_addr, &[addr, value])
-                .map_err(Error::Comm)?,
-        )
+    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c
+            .write_read(self.dev_addr, &[addr], buffer)
+            .map_err(Error::Comm)
     }
+}
+
+// Implementation of `AsyncInterface`
+#[cfg(feature = "async")]
+impl<I2C, CommE> AsyncInterface for I2cInterface<I2C>
+where
+    I2C: embedded_hal_async::i2c::I2c<Error = CommE>,
+{
+    type Error = Error<CommE>;
+    const MULTIBYTE: u8 = MULTIBYTE;
 
-    #[cfg(feature = "lps22hb")]
-    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {    
-        core::prelude::v1::Ok(
-            self.i2c                
-                .write_read(self.dev_addr, &[addr], buffer)
-                .map_err(Error::Comm)?,
-        )
+    async fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        self.i2c
+            .write(self.dev_addr, &[addr, value])
+            .await
+            .map_err(Error::Comm)
     }
 
-    #[cfg(feature = "lps25hb")]
-    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {    
-        let addr = addr | MULTIBYTE;
-        core::prelude::v1::Ok(
-            self.i2c                
-                .write_read(self.dev_addr, &[addr], buffer)
-                .map_err(Error::Comm)?,
-        )
+    async fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c
+            .write_read(self.dev_addr, &[addr], buffer)
+            .await
+            .map_err(Error::Comm)
     }
 }
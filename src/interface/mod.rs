@@ -3,10 +3,16 @@ pub mod spi;
 pub use self::spi::SpiInterface;
 pub mod i2c;
 pub use self::i2c::I2cInterface;
+#[cfg(feature = "embedded-hal-02")]
+pub mod eh02;
 
-/// Interface Trait. `SpiInterface` and `I2cInterface` implement this.
+/// Interface Trait. `SpiInterface` and `I2cInterface` implement this,
+/// with `embedded-hal` 1.0 buses (or 0.2 buses, see the `eh02` module).
 pub trait Interface {
     type Error;
+    /// Bit to be OR'ed with the register address to increment the address during multiple byte reads,
+    /// for the sensor models that need it (e.g. LPS25HB).
+    const MULTIBYTE: u8;
     /// Writes a byte to a sensor's specified register address.
     /// # Arguments    
     /// * `addr` - register address
@@ -18,3 +24,23 @@ pub trait Interface {
     /// * `buffer` - buffer to store read data
     fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
 }
+
+/// Async interface trait, implemented by `SpiInterface` and `I2cInterface` with `embedded-hal-async` buses.
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncInterface {
+    type Error;
+    /// Bit to be OR'ed with the register address to increment the address during multiple byte reads,
+    /// for the sensor models that need it (e.g. LPS25HB).
+    const MULTIBYTE: u8;
+    /// Writes a byte to a sensor's specified register address.
+    /// # Arguments    
+    /// * `addr` - register address
+    /// * `value` - value to write
+    async fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error>;
+    /// Reads multiple bytes from a sensor's specified register address.
+    /// # Arguments    
+    /// * `addr` - register address
+    /// * `buffer` - buffer to store read data
+    async fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+}
@@ -1,69 +1,74 @@
 //! SPI Interface
 use super::Interface;
-use embedded_hal::{blocking::spi::Transfer, blocking::spi::Write, digital::v2::OutputPin};
+#[cfg(feature = "async")]
+use super::AsyncInterface;
+use embedded_hal::spi::{Operation, SpiDevice};
 
 /// R/W bit should be high for SPI Read operation
-const SPI_READ: u8 = 0x80;
+pub(crate) const SPI_READ: u8 = 0x80;
 /// MS bit. When 0, does not increment the address; when 1, increments the address in multiple reads.
-
-#[cfg(feature="lps22hb")]
-const MS_BIT: u8 = 0x00;
-#[cfg(feature="lps25hb")]
-const MS_BIT: u8 = 0x40;
+pub(crate) const MS_BIT: u8 = 0x40;
 
 
 /// Errors in this crate
 #[derive(Debug)]
-pub enum Error<CommE, PinE> {
+pub enum Error<CommE> {
     /// Communication error
     Comm(CommE),
-    /// Pin setting error
-    Pin(PinE),
 }
 
-/// This combines the SPI Interface and chip select pins
-pub struct SpiInterface<SPI, CS> {
-    spi: SPI,    
-    cs: CS,
+/// This holds the SPI device (chip select and bus sharing are handled by the HAL)
+pub struct SpiInterface<SPI> {
+    spi: SPI,
 }
 
-impl<SPI, CS, CommE, PinE> SpiInterface<SPI, CS>
-where
-    SPI: Transfer<u8, Error = CommE> + Write<u8, Error = CommE>,    
-    CS: OutputPin<Error = PinE>,
-{
-    /// Initializes an Interface with `SPI` instance and chip select `OutputPin`s
+impl<SPI> SpiInterface<SPI> {
+    /// Initializes an Interface with `SpiDevice` instance
     /// # Arguments
-    /// * `spi` - SPI instance
-    /// * `cs` - Chip Select pin    
-    pub fn init(spi: SPI, cs: CS) -> Self {
-        Self { spi, cs }
+    /// * `spi` - SPI device
+    pub fn init(spi: SPI) -> Self {
+        Self { spi }
     }
 }
 
 /// Implementation of `Interface`
-impl<SPI, CS, CommE, PinE> Interface for SpiInterface<SPI, CS>
+impl<SPI, CommE> Interface for SpiInterface<SPI>
 where
-    SPI: Transfer<u8, Error = CommE> + Write<u8, Error = CommE>,
-    CS: OutputPin<Error = PinE>,    
+    SPI: SpiDevice<u8, Error = CommE>,
 {
-    type Error = Error<CommE, PinE>;
+    type Error = Error<CommE>;
+    const MULTIBYTE: u8 = MS_BIT;
 
     fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
         let bytes = [addr, value];
-        self.cs.set_low().map_err(Error::Pin)?;
-        self.spi.write(&bytes).map_err(Error::Comm)?;
-        self.cs.set_high().map_err(Error::Pin)?;
-        Ok(())
+        self.spi.write(&bytes).map_err(Error::Comm)
     }
-   
 
     fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.cs.set_low().map_err(Error::Pin)?;
-        self.spi.write(&[SPI_READ | MS_BIT | addr]).map_err(Error::Comm)?;
-        self.spi.transfer(buffer).map_err(Error::Comm)?;
-        self.cs.set_high().map_err(Error::Pin)?;        
-        Ok(())
+        self.spi
+            .transaction(&mut [Operation::Write(&[SPI_READ | addr]), Operation::Read(buffer)])
+            .map_err(Error::Comm)
     }
-    
-}
\ No newline at end of file
+}
+
+/// Implementation of `AsyncInterface`
+#[cfg(feature = "async")]
+impl<SPI, CommE> AsyncInterface for SpiInterface<SPI>
+where
+    SPI: embedded_hal_async::spi::SpiDevice<u8, Error = CommE>,
+{
+    type Error = Error<CommE>;
+    const MULTIBYTE: u8 = MS_BIT;
+
+    async fn write(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        let bytes = [addr, value];
+        self.spi.write(&bytes).await.map_err(Error::Comm)
+    }
+
+    async fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.spi
+            .transaction(&mut [Operation::Write(&[SPI_READ | addr]), Operation::Read(buffer)])
+            .await
+            .map_err(Error::Comm)
+    }
+}
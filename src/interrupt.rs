@@ -1,6 +1,14 @@
 //! Various functions related to interrupts
  
 use super::*;
+#[cfg(any(feature="lps22hb", feature="lps33hw", feature="lps35hw"))]
+use variant::lps22hb;
+#[cfg(any(feature = "lps22df", feature = "lps28dfw"))]
+use variant::lps22df;
+#[cfg(feature = "lps22hh")]
+use variant::lps22hh;
+#[cfg(feature = "lps25hb")]
+use variant::lps25hb;
  
 /// Interrupt pin settings
 #[derive(Debug)]
@@ -9,13 +17,13 @@ pub struct InterruptConfig {
     pub active_high_or_low: INT_ACTIVE, 
     /// configure interrupt pin as  push-pull or open drain
     pub pushpull_or_opendrain: INT_PIN,
-    /// configure data signal on the interrupt pin
+    /// configure data signal on the interrupt pin (not available on LPS22DF and LPS28DFW)
     pub data_signal_config: INT_DRDY,
-    #[cfg(feature="lps22hb")]
-    /// enable FIFO full flag on interrupt pin
+    #[cfg(any(feature="lps22hb", feature="lps33hw", feature="lps35hw", feature="lps22hh", feature="lps22df", feature="lps28dfw"))]
+    /// enable FIFO full flag on interrupt pin (all models except LPS25HB)
     pub enable_fifo_full: FLAG, 
     #[cfg(feature="lps25hb")]
-    /// enable FIFO empty flag on interrupt pin
+    /// enable FIFO empty flag on interrupt pin (LPS25HB only)
     pub enable_fifo_empty: FLAG,
     /// enable FIFO watermark flag on interrupt pin
     pub enable_fifo_fth: FLAG, 
@@ -24,6 +32,7 @@ pub struct InterruptConfig {
     /// enable data ready signal on interrupt pin
     pub enable_data_ready: FLAG,
     /// enable computing of differential pressure output
+    /// (pressure interrupts on the interrupt pin on LPS22DF and LPS28DFW)
     pub enable_differential: FLAG,
     /// enable latching interrupt request to INT_SOURCE register
     pub enable_latch_interrupt: FLAG,
@@ -39,7 +48,7 @@ impl Default for InterruptConfig {
             active_high_or_low: INT_ACTIVE::High,                // active high (CTRL_REG3)
             pushpull_or_opendrain: INT_PIN::PushPull,            // push-pull (CTRL_REG3)
             data_signal_config: INT_DRDY::DataSignal,            // data signal on INT_DRDY pin (CTRL_REG3)
-            #[cfg(feature="lps22hb")]
+            #[cfg(any(feature="lps22hb", feature="lps33hw", feature="lps35hw", feature="lps22hh", feature="lps22df", feature="lps28dfw"))]
             enable_fifo_full: FLAG::Disabled,                    // disabled (CTRL_REG3)
             #[cfg(feature="lps25hb")]
             enable_fifo_empty: FLAG::Disabled,                   // disabled (CTRL_REG4)
@@ -56,8 +65,8 @@ impl Default for InterruptConfig {
 
 impl InterruptConfig {
     /// Returns values to be written to CTRL_REG3, CTRL_REG4 and INTERRUPT_CFG:
-    #[cfg(feature="lps22hb")]
-    fn int_ctrl_reg3(&self) -> u8 {
+    #[cfg(any(feature="lps22hb", feature="lps33hw", feature="lps35hw"))]
+    pub(crate) fn lps22hb_ctrl_reg3(&self) -> u8 {
         let mut data = 0u8;
         if self.active_high_or_low.status() {
             data |= 1 << 7;
@@ -80,8 +89,72 @@ impl InterruptConfig {
         data |= self.data_signal_config.value();
         data
     }    
+    #[cfg(feature="lps22hh")]
+    pub(crate) fn lps22hh_ctrl_reg2(&self) -> u8 {
+        let mut data = 0u8;
+        if self.active_high_or_low.status() {
+            data |= 1 << 6;
+        }
+        if self.pushpull_or_opendrain.status() {
+            data |= 1 << 5;
+        }
+        data // this must be OR'ed with the content of CTRL_REG2
+    }
+
+    #[cfg(feature="lps22hh")]
+    pub(crate) fn lps22hh_ctrl_reg3(&self) -> u8 {
+        let mut data = 0u8;
+        if self.enable_fifo_full.status() {
+            data |= 1 << 5;
+        }
+        if self.enable_fifo_fth.status() {
+            data |= 1 << 4;
+        }
+        if self.enable_fifo_overrun.status() {
+            data |= 1 << 3;
+        }
+        if self.enable_data_ready.status() {
+            data |= 1 << 2;
+        }
+        data |= self.data_signal_config.value();
+        data
+    }
+
+    #[cfg(any(feature="lps22df", feature="lps28dfw"))]
+    pub(crate) fn lps22df_ctrl_reg3(&self) -> u8 {
+        let mut data = 0u8;
+        if self.active_high_or_low.status() {
+            data |= 1 << 3;
+        }
+        if self.pushpull_or_opendrain.status() {
+            data |= 1 << 1;
+        }
+        data // this must be OR'ed with the content of CTRL_REG3
+    }
+
+    #[cfg(any(feature="lps22df", feature="lps28dfw"))]
+    pub(crate) fn lps22df_ctrl_reg4(&self) -> u8 {
+        let mut data = 0u8;
+        if self.enable_data_ready.status() {
+            data |= 1 << 5;
+        }
+        if self.enable_differential.status() {
+            data |= 1 << 4;
+        }
+        if self.enable_fifo_full.status() {
+            data |= 1 << 2;
+        }
+        if self.enable_fifo_fth.status() {
+            data |= 1 << 1;
+        }
+        if self.enable_fifo_overrun.status() {
+            data |= 1;
+        }
+        data
+    }
+
     #[cfg(feature="lps25hb")]
-    fn int_ctrl_reg3(&self) -> u8 {
+    pub(crate) fn lps25hb_ctrl_reg3(&self) -> u8 {
         let mut data = 0u8;
         if self.active_high_or_low.status() {
             data |= 1 << 7;
@@ -95,7 +168,7 @@ impl InterruptConfig {
     }
 
     #[cfg(feature="lps25hb")]
-    fn int_ctrl_reg4(&self) -> u8 {
+    pub(crate) fn lps25hb_ctrl_reg4(&self) -> u8 {
         let mut data = 0u8;
         if self.enable_fifo_empty.status() {
             data |= 1 << 3;
@@ -112,7 +185,86 @@ impl InterruptConfig {
         data
     }
 
-    fn int_interrupt_cfg(&self) -> u8 {
+    /// Decodes the content of CTRL_REG3 and INTERRUPT_CFG
+    #[cfg(any(feature="lps22hb", feature="lps33hw", feature="lps35hw"))]
+    #[allow(clippy::needless_update)]
+    pub(crate) fn from_lps22hb_registers(ctrl_reg3: u8, interrupt_cfg: u8) -> Self {
+        InterruptConfig {
+            active_high_or_low: INT_ACTIVE::from_status(ctrl_reg3 & lps22hb::Bitmasks::INT_H_L != 0),
+            pushpull_or_opendrain: INT_PIN::from_status(ctrl_reg3 & lps22hb::Bitmasks::PP_OD != 0),
+            data_signal_config: INT_DRDY::from_value(ctrl_reg3 & lps22hb::Bitmasks::INT_S_MASK),
+            enable_fifo_full: FLAG::from_status(ctrl_reg3 & lps22hb::Bitmasks::F_FSS5 != 0),
+            enable_fifo_fth: FLAG::from_status(ctrl_reg3 & lps22hb::Bitmasks::F_FTH != 0),
+            enable_fifo_overrun: FLAG::from_status(ctrl_reg3 & lps22hb::Bitmasks::F_OVR != 0),
+            enable_data_ready: FLAG::from_status(ctrl_reg3 & lps22hb::Bitmasks::DRDY != 0),
+            enable_differential: FLAG::from_status(interrupt_cfg & lps22hb::Bitmasks::DIFF_EN != 0),
+            enable_latch_interrupt: FLAG::from_status(interrupt_cfg & lps22hb::Bitmasks::LIR != 0),
+            enable_low_event: FLAG::from_status(interrupt_cfg & lps22hb::Bitmasks::PLE != 0),
+            enable_high_event: FLAG::from_status(interrupt_cfg & lps22hb::Bitmasks::PHE != 0),
+            ..Self::default()
+        }
+    }
+
+    /// Decodes the content of CTRL_REG2, CTRL_REG3 and INTERRUPT_CFG
+    #[cfg(feature="lps22hh")]
+    #[allow(clippy::needless_update)]
+    pub(crate) fn from_lps22hh_registers(ctrl_reg2: u8, ctrl_reg3: u8, interrupt_cfg: u8) -> Self {
+        InterruptConfig {
+            active_high_or_low: INT_ACTIVE::from_status(ctrl_reg2 & lps22hh::Bitmasks::INT_H_L != 0),
+            pushpull_or_opendrain: INT_PIN::from_status(ctrl_reg2 & lps22hh::Bitmasks::PP_OD != 0),
+            data_signal_config: INT_DRDY::from_value(ctrl_reg3 & lps22hh::Bitmasks::INT_S_MASK),
+            enable_fifo_full: FLAG::from_status(ctrl_reg3 & lps22hh::Bitmasks::INT_F_FULL != 0),
+            enable_fifo_fth: FLAG::from_status(ctrl_reg3 & lps22hh::Bitmasks::INT_F_WTM != 0),
+            enable_fifo_overrun: FLAG::from_status(ctrl_reg3 & lps22hh::Bitmasks::INT_F_OVR != 0),
+            enable_data_ready: FLAG::from_status(ctrl_reg3 & lps22hh::Bitmasks::DRDY != 0),
+            enable_differential: FLAG::from_status(interrupt_cfg & lps22hh::Bitmasks::DIFF_EN != 0),
+            enable_latch_interrupt: FLAG::from_status(interrupt_cfg & lps22hh::Bitmasks::LIR != 0),
+            enable_low_event: FLAG::from_status(interrupt_cfg & lps22hh::Bitmasks::PLE != 0),
+            enable_high_event: FLAG::from_status(interrupt_cfg & lps22hh::Bitmasks::PHE != 0),
+            ..Self::default()
+        }
+    }
+
+    /// Decodes the content of CTRL_REG3, CTRL_REG4 and INTERRUPT_CFG (LPS22DF and LPS28DFW)
+    #[cfg(any(feature="lps22df", feature="lps28dfw"))]
+    #[allow(clippy::needless_update)]
+    pub(crate) fn from_lps22df_registers(ctrl_reg3: u8, ctrl_reg4: u8, interrupt_cfg: u8) -> Self {
+        InterruptConfig {
+            active_high_or_low: INT_ACTIVE::from_status(ctrl_reg3 & lps22df::Bitmasks::INT_H_L != 0),
+            pushpull_or_opendrain: INT_PIN::from_status(ctrl_reg3 & lps22df::Bitmasks::PP_OD != 0),
+            enable_fifo_full: FLAG::from_status(ctrl_reg4 & lps22df::Bitmasks::INT_F_FULL != 0),
+            enable_fifo_fth: FLAG::from_status(ctrl_reg4 & lps22df::Bitmasks::INT_F_WTM != 0),
+            enable_fifo_overrun: FLAG::from_status(ctrl_reg4 & lps22df::Bitmasks::INT_F_OVR != 0),
+            enable_data_ready: FLAG::from_status(ctrl_reg4 & lps22df::Bitmasks::DRDY != 0),
+            enable_differential: FLAG::from_status(ctrl_reg4 & lps22df::Bitmasks::INT_EN != 0),
+            enable_latch_interrupt: FLAG::from_status(interrupt_cfg & lps22df::Bitmasks::LIR != 0),
+            enable_low_event: FLAG::from_status(interrupt_cfg & lps22df::Bitmasks::PLE != 0),
+            enable_high_event: FLAG::from_status(interrupt_cfg & lps22df::Bitmasks::PHE != 0),
+            ..Self::default()
+        }
+    }
+
+    /// Decodes the content of CTRL_REG1, CTRL_REG3, CTRL_REG4 and INTERRUPT_CFG
+    #[cfg(feature="lps25hb")]
+    #[allow(clippy::needless_update)]
+    pub(crate) fn from_lps25hb_registers(ctrl_reg1: u8, ctrl_reg3: u8, ctrl_reg4: u8, interrupt_cfg: u8) -> Self {
+        InterruptConfig {
+            active_high_or_low: INT_ACTIVE::from_status(ctrl_reg3 & lps25hb::Bitmasks::INT_H_L != 0),
+            pushpull_or_opendrain: INT_PIN::from_status(ctrl_reg3 & lps25hb::Bitmasks::PP_OD != 0),
+            data_signal_config: INT_DRDY::from_value(ctrl_reg3 & lps25hb::Bitmasks::INT_S_MASK),
+            enable_fifo_empty: FLAG::from_status(ctrl_reg4 & lps25hb::Bitmasks::F_EMPTY != 0),
+            enable_fifo_fth: FLAG::from_status(ctrl_reg4 & lps25hb::Bitmasks::F_FTH != 0),
+            enable_fifo_overrun: FLAG::from_status(ctrl_reg4 & lps25hb::Bitmasks::F_OVR != 0),
+            enable_data_ready: FLAG::from_status(ctrl_reg4 & lps25hb::Bitmasks::DRDY != 0),
+            enable_differential: FLAG::from_status(ctrl_reg1 & lps25hb::Bitmasks::DIFF_EN != 0),
+            enable_latch_interrupt: FLAG::from_status(interrupt_cfg & lps25hb::Bitmasks::LIR != 0),
+            enable_low_event: FLAG::from_status(interrupt_cfg & lps25hb::Bitmasks::PL_E != 0),
+            enable_high_event: FLAG::from_status(interrupt_cfg & lps25hb::Bitmasks::PH_E != 0),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn int_interrupt_cfg(&self) -> u8 {
         
         let mut data = 0u8;
 
@@ -137,74 +289,165 @@ pub struct IntStatus {
     pub diff_press_high: bool,    
 }
 
-impl<T, E> LPS2X<T>
-where
-    T: Interface<Error = E>,
-{
-    /// Enable interrupts and configure the interrupt pin
-    #[cfg(feature="lps22hb")]
-    pub fn configure_interrupts(&mut self, flag: bool, config: InterruptConfig,) -> Result<(), T::Error> {
-        match config.enable_differential {
-            FLAG::Enabled => self.set_register_bit_flag(Registers::INTERRUPT_CFG, Bitmasks::DIFF_EN),
-            FLAG::Disabled => self.clear_register_bit_flag(Registers::INTERRUPT_CFG, Bitmasks::DIFF_EN),
-        }?;
-        self.interface
-            .write(Registers::CTRL_REG3.addr(), config.int_ctrl_reg3())?;        
-        
-        let mut buffer = [0u8;1];
-        self.read_register(Registers::INTERRUPT_CFG)?;        
-        let mut interrupt_cfg = 0u8;
-        interrupt_cfg |= config.int_interrupt_cfg();
-       
-        self.interface
-            .write(Registers::INTERRUPT_CFG.addr(), interrupt_cfg)?;
-        Ok(())
+impl IntStatus {
+    /// Decode the content of the INT_SOURCE register
+    pub(crate) fn from_register<C: Variant>(reg_value: u8) -> Self {
+        IntStatus {
+            // Has any interrupt event been generated?
+            interrupt_active: reg_value & C::IA != 0,
+            // Has low differential pressure event been generated?
+            diff_press_low: reg_value & C::PL != 0,
+            // Has high differential pressure event been generated?
+            diff_press_high: reg_value & C::PH != 0,
+        }
     }
+}
 
-    #[cfg(feature="lps25hb")]
-    /// Configure interrupt pin and interrupt sources, enable and configure differential pressure interrupts
-    pub fn configure_interrupts(
-        &mut self,
-        //flag: bool,
-        config: InterruptConfig,
-    ) -> Result<(), T::Error> {
-        match config.enable_differential {
-            FLAG::Enabled => self.set_register_bit_flag(Registers::CTRL_REG1, Bitmasks::DIFF_EN),
-            FLAG::Disabled => self.clear_register_bit_flag(Registers::CTRL_REG1, Bitmasks::DIFF_EN),
-        }?;
-
-        self.interface
-            .write(Registers::CTRL_REG3.addr(), config.int_ctrl_reg3())?;
-        self.interface
-            .write(Registers::CTRL_REG4.addr(), config.int_ctrl_reg4())?;
-        self.interface
-            .write(Registers::INTERRUPT_CFG.addr(), config.int_interrupt_cfg())?;
-        Ok(())
-    }
+macro_rules! impl_interrupts {
+    ($driver:ident $(<$pin:ident>)?, $interface:ident; $($async:ident)?; $($await:ident)?) => {
+        impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            C: Variant,
+            MODE: PowerMode,
+        {
+            /// Get all the flags from the INT_SOURCE register (NOTE: INT_SOURCE register is cleared by reading it)
+            pub $($async)? fn get_int_status(&mut self) -> Result<IntStatus, Error<E>> {        
 
-    /// Get all the flags from the INT_SOURCE register (NOTE: INT_SOURCE register is cleared by reading it)
-    pub fn get_int_status(&mut self) -> Result<IntStatus, T::Error> {        
-                    
-        let reg_value = self.read_register(Registers::INT_SOURCE)?;
-
-        let status = IntStatus {
-            /// Has any interrupt event been generated?
-            interrupt_active: match reg_value & Bitmasks::IA {
-                0 => false,
-                _ => true,
-            },
-            /// Has low differential pressure event been generated?
-            diff_press_low: match reg_value & Bitmasks::PL {
-                0 => false,
-                _ => true,
-            },
-            /// Has high differential pressure event been generated?
-            diff_press_high: match reg_value & Bitmasks::PH {
-                0 => false,
-                _ => true,
-            },           
-        };
-        Ok(status)
-    }
+                let reg_value = self.read_register(C::INT_SOURCE) $(.$await)? ?;
+                Ok(IntStatus::from_register::<C>(reg_value))
+            }
+        }
+
+        #[cfg(any(feature="lps22hb", feature="lps33hw", feature="lps35hw"))]
+        impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            C: Variant<ODR = lps22hb::ODR>,
+            MODE: PowerMode,
+        {
+            /// Configure interrupt pin and interrupt sources, enable and configure differential pressure interrupts
+            pub $($async)? fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+                match config.enable_differential {
+                    FLAG::Enabled => self.set_register_bit_flag(lps22hb::Registers::INTERRUPT_CFG.addr(), lps22hb::Bitmasks::DIFF_EN) $(.$await)?,
+                    FLAG::Disabled => self.clear_register_bit_flag(lps22hb::Registers::INTERRUPT_CFG.addr(), lps22hb::Bitmasks::DIFF_EN) $(.$await)?,
+                }?;
+                self.write_register(lps22hb::Registers::CTRL_REG3.addr(), config.lps22hb_ctrl_reg3()) $(.$await)? ?;        
+
+                // keep the differential and autozero settings, replace the interrupt request settings
+                let mut interrupt_cfg = self.read_register(lps22hb::Registers::INTERRUPT_CFG.addr()) $(.$await)? ?;
+                interrupt_cfg &= !(lps22hb::Bitmasks::LIR | lps22hb::Bitmasks::PLE | lps22hb::Bitmasks::PHE);
+                interrupt_cfg |= config.int_interrupt_cfg();
+
+                self.write_register(lps22hb::Registers::INTERRUPT_CFG.addr(), interrupt_cfg) $(.$await)? ?;
+                Ok(())
+            }
+        }
+
+        #[cfg(feature="lps22hh")]
+        impl<T, MODE, $($pin,)? E> $driver<T, LPS22HH, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            MODE: PowerMode,
+        {
+            /// Configure interrupt pin and interrupt sources, enable and configure differential pressure interrupts
+            pub $($async)? fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+                // the pin settings are in CTRL_REG2, keep the other bits
+                let mut ctrl_reg2 = self.read_register(lps22hh::Registers::CTRL_REG2.addr()) $(.$await)? ?;
+                ctrl_reg2 &= !(lps22hh::Bitmasks::INT_H_L | lps22hh::Bitmasks::PP_OD);
+                ctrl_reg2 |= config.lps22hh_ctrl_reg2();
+                self.write_register(lps22hh::Registers::CTRL_REG2.addr(), ctrl_reg2) $(.$await)? ?;
+
+                self.write_register(lps22hh::Registers::CTRL_REG3.addr(), config.lps22hh_ctrl_reg3()) $(.$await)? ?;
+
+                // keep the autozero settings, replace the differential and interrupt request settings
+                let mut interrupt_cfg = self.read_register(lps22hh::Registers::INTERRUPT_CFG.addr()) $(.$await)? ?;
+                interrupt_cfg &= !(lps22hh::Bitmasks::DIFF_EN | lps22hh::Bitmasks::LIR | lps22hh::Bitmasks::PLE | lps22hh::Bitmasks::PHE);
+                if config.enable_differential.status() {
+                    interrupt_cfg |= lps22hh::Bitmasks::DIFF_EN;
+                }
+                interrupt_cfg |= config.int_interrupt_cfg();
+                self.write_register(lps22hh::Registers::INTERRUPT_CFG.addr(), interrupt_cfg) $(.$await)? ?;
+                Ok(())
+            }
+        }
+
+        #[cfg(any(feature="lps22df", feature="lps28dfw"))]
+        impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            C: Variant<ODR = lps22df::ODR>,
+            MODE: PowerMode,
+        {
+            /// Interrupt configuration, common to LPS22DF and LPS28DFW
+            $($async)? fn configure_lps22df_interrupts(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+                // the pin settings are in CTRL_REG3, keep the address incrementing bit
+                let mut ctrl_reg3 = self.read_register(lps22df::Registers::CTRL_REG3.addr()) $(.$await)? ?;
+                ctrl_reg3 &= !(lps22df::Bitmasks::INT_H_L | lps22df::Bitmasks::PP_OD);
+                ctrl_reg3 |= config.lps22df_ctrl_reg3();
+                self.write_register(lps22df::Registers::CTRL_REG3.addr(), ctrl_reg3) $(.$await)? ?;
+
+                // keep the data ready pulse setting
+                let mut ctrl_reg4 = self.read_register(lps22df::Registers::CTRL_REG4.addr()) $(.$await)? ?;
+                ctrl_reg4 &= lps22df::Bitmasks::DRDY_PLS;
+                ctrl_reg4 |= config.lps22df_ctrl_reg4();
+                self.write_register(lps22df::Registers::CTRL_REG4.addr(), ctrl_reg4) $(.$await)? ?;
+
+                // keep the autozero settings, replace the interrupt request settings
+                let mut interrupt_cfg = self.read_register(lps22df::Registers::INTERRUPT_CFG.addr()) $(.$await)? ?;
+                interrupt_cfg &= !(lps22df::Bitmasks::LIR | lps22df::Bitmasks::PLE | lps22df::Bitmasks::PHE);
+                interrupt_cfg |= config.int_interrupt_cfg();
+                self.write_register(lps22df::Registers::INTERRUPT_CFG.addr(), interrupt_cfg) $(.$await)? ?;
+                Ok(())
+            }
+        }
+
+        #[cfg(feature="lps22df")]
+        impl<T, MODE, $($pin,)? E> $driver<T, LPS22DF, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            MODE: PowerMode,
+        {
+            /// Configure interrupt pin and interrupt sources, enable pressure interrupts
+            pub $($async)? fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+                self.configure_lps22df_interrupts(config) $(.$await)?
+            }
+        }
+
+        #[cfg(feature="lps28dfw")]
+        impl<T, MODE, $($pin,)? E> $driver<T, LPS28DFW, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            MODE: PowerMode,
+        {
+            /// Configure interrupt pin and interrupt sources, enable pressure interrupts
+            pub $($async)? fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+                self.configure_lps22df_interrupts(config) $(.$await)?
+            }
+        }
+
+        #[cfg(feature="lps25hb")]
+        impl<T, MODE, $($pin,)? E> $driver<T, LPS25HB, MODE $(, $pin)?>
+        where
+            T: $interface<Error = E>,
+            MODE: PowerMode,
+        {
+            /// Configure interrupt pin and interrupt sources, enable and configure differential pressure interrupts
+            pub $($async)? fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+                match config.enable_differential {
+                    FLAG::Enabled => self.set_register_bit_flag(lps25hb::Registers::CTRL_REG1.addr(), lps25hb::Bitmasks::DIFF_EN) $(.$await)?,
+                    FLAG::Disabled => self.clear_register_bit_flag(lps25hb::Registers::CTRL_REG1.addr(), lps25hb::Bitmasks::DIFF_EN) $(.$await)?,
+                }?;
+
+                self.write_register(lps25hb::Registers::CTRL_REG3.addr(), config.lps25hb_ctrl_reg3()) $(.$await)? ?;
+                self.write_register(lps25hb::Registers::CTRL_REG4.addr(), config.lps25hb_ctrl_reg4()) $(.$await)? ?;
+                self.write_register(lps25hb::Registers::INTERRUPT_CFG.addr(), config.int_interrupt_cfg()) $(.$await)? ?;
+                Ok(())
+            }
+        }
+    };
+}
 
-}
\ No newline at end of file
+impl_interrupts!(LPS2X, Interface; ;);
+#[cfg(feature = "async")]
+impl_interrupts!(LPS2XAsync<P>, AsyncInterface; async; await);
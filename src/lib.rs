@@ -1,20 +1,33 @@
-//! A platform agnostic driver to interface with LPS22HB and LPS25HB pressure sensor modules.
+//! A platform agnostic driver to interface with LPS22HB, LPS22HH, LPS22DF, LPS25HB, LPS28DFW, LPS33HW and LPS35HW pressure sensor modules.
+ //!
+ //! The sensor model is selected with a type parameter, see the [`variant`] module.
+ //! Each model must be enabled with the corresponding feature; several models can be enabled at the same time.
+ //! The power mode (power-down, one-shot, continuous) is also part of the driver type, see the [`mode`] module.
+ //! An async driver is available with the `async` feature, see the `asynch` module.
  //!
  //! This driver allows you to:
- //! - read atmospheric pressure in hPa, see [`read_pressure()`]
- //! - enable single-shot data acquisition, see [`enable_one_shot()`]
+ //! - read atmospheric pressure and temperature as [`Pressure`] and [`Temperature`] quantities, see [`read_pressure()`]
+ //!   and [`read_temperature()`]
+ //! - run one-shot measurements, see [`into_one_shot()`] and [`start_measurement()`]
  //! - set data rate, see [`set_datarate()`]
  //!
-//! [`read_pressure()`]: struct.LPS2x.html#method.read_pressure
-//! [`read_temperature()`]: struct.LPS2x.html#method.read_temperature
-//! [`enable_one_shot()`]: struct.LPS2x.html#method.enable_one_shot
-//! [`set_datarate()`]: struct.LPS2x.html#method.set_datarate
+//! [`read_pressure()`]: LPS2X::read_pressure
+//! [`read_temperature()`]: LPS2X::read_temperature
+//! [`into_one_shot()`]: LPS2X::into_one_shot
+//! [`start_measurement()`]: LPS2X::start_measurement
+//! [`set_datarate()`]: LPS2X::set_datarate
+//!
+//! The sensor is connected through an I2C or SPI bus, see the [`interface`] module
+//! (`I2cInterface`, `SpiInterface` in 4-wire mode).
 //!
-//! __NOTE__: Only I2C interface is supported at the moment.
-//!  //!
 //! ### Datasheets: 
 //! - [LPS22HB](https://www.st.com/resource/en/datasheet/lps22hb.pdf)
+//! - [LPS22HH](https://www.st.com/resource/en/datasheet/lps22hh.pdf)
+//! - [LPS22DF](https://www.st.com/resource/en/datasheet/lps22df.pdf)
 //! - [LPS25HB](https://www.st.com/resource/en/datasheet/lps25hb.pdf)
+//! - [LPS28DFW](https://www.st.com/resource/en/datasheet/lps28dfw.pdf)
+//! - [LPS33HW](https://www.st.com/resource/en/datasheet/lps33hw.pdf)
+//! - [LPS35HW](https://www.st.com/resource/en/datasheet/lps35hw.pdf)
  //!
  //! ## Usage examples (see also examples folder)
  //!
@@ -47,30 +60,45 @@
 //! - reboot
 //! - software reset
 
-// TO DO: move MULTIBYTE into the interface, as it is different between I2C and SPI 
-// TO DO (IDEA): create an init() function with a Config struct. 
-// The configuration could include: power on (bool), ODR, block data update (bool), pressure resolution, temperature resolution.
-//
 // TO DO: REMOVE PUB FROM READ_REGISTER() FUNCTION AFTER THE TESTS 
 // 
 
 #![no_std]
 
-#[cfg(not(any(feature = "lps25hb", feature = "lps22hb")))]
-compile_error!("One of the sensor models must be selected using --features");
-
-/*
-#[cfg(all(feature = "lps25hb", feature = "lps22hb"))]
-compile_error!("Only one of the sensor models can be selected");
-*/
+#[cfg(not(any(feature = "lps25hb", feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw")))]
+compile_error!("At least one of the sensor models must be selected using --features");
 
+use core::marker::PhantomData;
 
 pub mod sensor;
+
+pub mod units;
+pub use units::{Pressure, Temperature};
+
+pub mod convert;
+
+pub mod calibration;
+
+pub mod compensation;
  
 pub mod config;
 
-pub mod register;
-use register::{Registers, Bitmasks};
+pub mod variant;
+pub use variant::Variant;
+#[cfg(feature = "lps22hb")]
+pub use variant::LPS22HB;
+#[cfg(feature = "lps33hw")]
+pub use variant::LPS33HW;
+#[cfg(feature = "lps35hw")]
+pub use variant::LPS35HW;
+#[cfg(feature = "lps22df")]
+pub use variant::LPS22DF;
+#[cfg(feature = "lps22hh")]
+pub use variant::LPS22HH;
+#[cfg(feature = "lps25hb")]
+pub use variant::LPS25HB;
+#[cfg(feature = "lps28dfw")]
+pub use variant::LPS28DFW;
  
 pub mod fifo;
 
@@ -78,196 +106,197 @@ pub mod interrupt;
  
 pub mod interface;
 use interface::Interface;
+#[cfg(feature = "async")]
+use interface::AsyncInterface;
+
+// without a sensor model only the `compile_error!` above is reported
+#[cfg(any(feature = "lps25hb", feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+pub mod device;
+#[cfg(any(feature = "lps25hb", feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw"))]
+pub use device::{Device, DeviceModel};
+#[cfg(all(feature = "async", any(feature = "lps25hb", feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps22df", feature = "lps28dfw")))]
+pub use device::DeviceAsync;
+
+pub mod mode;
+use mode::PowerMode;
+pub use mode::{Continuous, OneShot, PowerDown};
+
+#[cfg(feature = "async")]
+pub mod asynch;
+
+#[cfg(test)]
+mod mock;
+#[cfg(feature = "async")]
+pub use asynch::LPS2XAsync;
  
-/// Sensor's ID
-//const WHOAMI: u8 = 0b10110001; // decimal value 177 (LPS22HB)
-// const WHOAMI: u8 = 0b10111101; // decimal value 189 (LPS25HB)
-#[cfg(feature = "lps22hb")]
-/// The output of the temperature sensor must be divided by 100, see p. 10 of the datasheet.
-const TEMP_SCALE: f32 = 100.0;
-/// The output of the pressure sensor must be divided by 4096, see p. 10 of the datasheet.
-// https://www.st.com/resource/en/technical_note/dm00242307-how-to-interpret-pressure-and-temperature-readings-in-the-lps25hb-pressure-sensor-stmicroelectronics.pdf
+/// The output of the pressure sensor must be divided by 4096, see Table 3 of the datasheets
+/// (except LPS28DFW in 4060 hPa full-scale mode, see `lps28dfw::FS_MODE`).
+pub const PRESS_SENSITIVITY: u16 = 4096;
+
+/// Maximum number of register reads while waiting for a self-clearing bit
+const POLL_LIMIT: u16 = 1000;
+
+/// Errors returned by the driver
+#[derive(Debug)]
+pub enum Error<E> {
+     /// Interface (bus) error
+     Interface(E),
+     /// The WHO_AM_I register returned an ID that doesn't belong to the expected sensor model
+     /// (or to any of the enabled models when detecting the sensor)
+     UnknownDevice(u8),
+     /// The WHO_AM_I register returned an ID shared by several of the enabled models (e.g. 0xB1 with the
+     /// `lps22hb` and `lps33hw` features), use `Device::detect_as()` to select the model
+     AmbiguousDevice(u8),
+     /// The sensor didn't complete an operation in time (e.g. the BOOT bit never cleared)
+     Timeout,
+     /// The value doesn't fit in the register (e.g. pressure threshold, FIFO watermark level)
+     OutOfRange,
+     /// The requested settings can't be used together, or not in the current mode
+     InvalidConfig,
+     /// The data ready pin couldn't be read (async driver), with the kind of the pin error
+     Pin(embedded_hal::digital::ErrorKind),
+     /// A value written to the device didn't read back the same (e.g. calibration offset)
+     Mismatch,
+}
 
-#[cfg(feature = "lps25hb")]
-/// The output of the temperature sensor must be divided by 480, see Table 3 of the datasheet.
-const TEMP_SCALE: f32 = 480.0;
-/// An offset value must be added to the result. This is NOT mentioned in the LPS25HB datasheet, but is described in the LPS25H datasheet.
-#[cfg(feature = "lps25hb")]
-const TEMP_OFFSET: f32 = 42.5;
-/// The output of the pressure sensor must be divided by 4096, see Table 3 of the datasheet.
-const PRESS_SCALE: f32 = 4096.0;
+/// Lets `?` convert the result of a failed mode transition (the driver and the error), dropping the driver
+impl<D, E> From<(D, Error<E>)> for Error<E> {
+     fn from((_, error): (D, Error<E>)) -> Self {
+          error
+     }
+}
  
- /// Holds the driver instance with the selected interface
-pub struct LPS2X<T> {
-
+/// Holds the driver instance with the selected interface, sensor model and power mode
+pub struct LPS2X<T, C, MODE = PowerDown> {
      interface: T,
+     /// Pressure sensitivity in LSB/hPa, updated by `init()` (and `full_scale()` on LPS28DFW)
+     press_sensitivity: u16,
+     /// Autozero function enabled (the pressure output is relative to REF_P), updated by `autozero_config()`
+     autozero: bool,
+     /// FIFO setup of the driver, updated by `configure_fifo()`, `arm_capture()`, `high_resolution_mode()`
+     /// and the output data rate changes
+     fifo_use: fifo::FifoUse,
+     variant: PhantomData<C>,
+     mode: PhantomData<MODE>,
 }
  
 
-impl<T, E> LPS2X<T>
+impl<T, C, E> LPS2X<T, C, PowerDown>
 where
      T: Interface<Error = E>,
+     C: Variant,
  {
-     /// Create a new instance of the LPS25HB driver.
-     pub fn new(interface: T) -> Self {
-          LPS2X { interface }
-     }
- 
-     /// Destroy driver instance, return interface instance.
-     pub fn destroy(self) -> T {
-          self.interface
-     }
- 
-
-     /// Read a byte from the given register.
-     fn read_register(&mut self, address: Registers) -> Result<u8, T::Error> {
-     // pub fn read_register(&mut self, address: Registers) -> Result<u8, T::Error> {
-         let mut reg_data = [0u8];
-         self.interface.read(address.addr(), &mut reg_data)?;
-         Ok(reg_data[0])
+     /// Create a new instance of the driver for the given sensor model, e.g. `LPS2X::new(interface, LPS25HB)`.
+     /// The sensor is expected to be in power-down mode (power-on default), use `init()`
+     /// or one of the `into_*()` functions to put it in a known mode.
+     /// The default pressure sensitivity is assumed until `init()` is called.
+     pub fn new(interface: T, _variant: C) -> Self {
+          LPS2X {
+               interface,
+               press_sensitivity: PRESS_SENSITIVITY,
+               autozero: false,
+               fifo_use: fifo::FifoUse::Other,
+               variant: PhantomData,
+               mode: PhantomData,
+          }
      }
  
-     /// Clear selected bits using a bitmask
-     fn clear_register_bit_flag(&mut self, address: Registers, bitmask: u8) -> Result<(), T::Error> {
-        let mut reg_data = [0u8];
-        self.interface.read(address.addr(), &mut reg_data)?;
-        let payload: u8 = reg_data[0] & !bitmask;
-        self.interface.write(address.addr(), payload)?;
-        Ok(())
-     }
- 
-     /// Set selected bits using a bitmask
-     fn set_register_bit_flag(&mut self, address: Registers, bitmask: u8) -> Result<(), T::Error> {
-         let mut reg_data = [0u8];
-         self.interface.read(address.addr(), &mut reg_data)?;
-         let payload: u8 = reg_data[0] | bitmask;
-         self.interface.write(address.addr(), payload)?;
-         Ok(())
-     }
-
-     /// Check if specific bits are set.
-     fn is_register_bit_flag_high(
-          &mut self,
-          address: Registers,
-          bitmask: u8,
-     ) -> Result<bool, T::Error> {
-          let data = self.read_register(address)?;
-          Ok((data & bitmask) != 0)
+     /// Create a new instance of the driver for the given sensor model,
+     /// after checking the content of the WHO_AM_I register.
+     pub fn new_checked(interface: T, variant: C) -> Result<Self, Error<E>> {
+          let mut sensor = Self::new(interface, variant);
+          let whoami = sensor.get_device_id()?;
+          if whoami != C::WHOAMI {
+               return Err(Error::UnknownDevice(whoami));
+          }
+          Ok(sensor)
      }
 }
-  
-/// Output data rate and power mode selection (ODR). (Refer to datasheets)
-
-// THERE ARE CONFLICTS HERE AS SAME FREQUENCIES MAY HAVE DIFFERENT BIT VALUES
 
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy)]
-pub enum ODR {
-    /// Power-down / One-shot mode enabled
-    //PowerDown = 0b000,
-    /// One-shot mode enabled
-    OneShot = 0b000, // KEEPING THIS AS A NAME
-    /// 1 Hz
-    _1Hz = 0b001, // same for both sensors
-    #[cfg(feature = "lps25hb")]
-    /// 7 Hz
-    _7Hz = 0b010,
-    #[cfg(feature = "lps22hb")]
-    /// 10 Hz
-    _10Hz = 0b010,    
-    #[cfg(feature = "lps25hb")]
-    /// 12.5 Hz    
-    _12_5Hz = 0b011,
-    #[cfg(feature = "lps25hb")]
-    /// 25 Hz
-    _25Hz = 0b100,
-    #[cfg(feature = "lps22hb")]
-    /// 25 Hz
-    _25Hz = 0b011,
-    #[cfg(feature = "lps22hb")]
-    /// 50 Hz    
-    _50Hz = 0b100,
-    #[cfg(feature = "lps22hb")]
-    /// 75 Hz
-    _75Hz = 0b101,
-
-}
- 
-impl ODR {
-     pub fn value(self) -> u8 {
-        (self as u8) << 4 // shifted into the right position, can be used directly
+impl<T, C, MODE, E> LPS2X<T, C, MODE>
+where
+     T: Interface<Error = E>,
+     C: Variant,
+     MODE: PowerMode,
+ {
+     /// Destroy driver instance, return interface instance.
+     pub fn destroy(self) -> T {
+          self.interface
      }
 }
 
-/// SPI interface mode
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy)]
-pub enum SPI_Mode {
-    /// 4-wire mode (default)
-    _4wire,
-    /// 3-wire mode
-    _3wire,
-}
-
- 
-
-/// FIFO mode selection. (Refer to datasheets)
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy)]
-pub enum FIFO_MODE {
-    /// Bypass mode
-    Bypass = 0b000,
-    /// FIFO mode
-    FIFO = 0b001,
-    /// Stream mode
-    Stream = 0b010,
-    /// Stream-to-FIFO mode
-    Stream_to_FIFO = 0b011,
-    /// Bypass-to-stream mode
-    Bypass_to_stream = 0b100,
-    #[cfg(feature = "lps22hb")]
-    /// Dynamic-stream mode    
-    Dynamic_Stream = 0b110, // LPS22-specific
-    #[cfg(feature = "lps25hb")]
-    /// FIFO Mean mode
-    FIFO_Mean = 0b110, // LPS25-specific
-    /// Bypass-to-FIFO mode
-    Bypass_to_FIFO = 0b111,
-}
- 
-impl FIFO_MODE {
-     pub fn value(self) -> u8 {
-
-        (self as u8) << 5 // shifted into the right position, can be used directly
-    }
-}
-
-/// FIFO Mean mode running average sample size. (Refer to Table 23)
-
-#[cfg(feature = "lps25hb")]
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy)]
-pub enum FIFO_MEAN {
-    /// 2-sample moving average
-    _2sample = 0b00001,
-    /// 4-sample moving average
-    _4sample = 0b00011,
-    /// 8-sample moving average
-    _8sample = 0b00111,
-    /// 16-sample moving average
-    _16sample = 0b01111,
-    /// 32-sample moving average
-    _32sample = 0b11111,
-}
-
-#[cfg(feature = "lps25hb")]
-impl FIFO_MEAN {
-    pub fn value(self) -> u8 {
-        self as u8 // no need to shift, bits 0:4
-     }
+// The functions of the blocking and async drivers are generated by the same macros,
+// invoked once for `LPS2X` and once for `LPS2XAsync` (with the `async` keyword and `.await` calls).
+macro_rules! impl_registers {
+     ($driver:ident $(<$pin:ident>)?, $interface:ident; $($async:ident)?; $($await:ident)?) => {
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               MODE: PowerMode,
+          {
+               /// Read a byte from the given register.
+               $($async)? fn read_register(&mut self, address: u8) -> Result<u8, Error<E>> {
+                    let mut reg_data = [0u8];
+                    self.interface.read(address, &mut reg_data) $(.$await)? .map_err(Error::Interface)?;
+                    Ok(reg_data[0])
+               }
+
+               /// Read consecutive registers starting from the given address,
+               /// requesting address incrementing if the sensor model needs it.
+               $($async)? fn read_registers(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error<E>> {
+                    let address = match C::MULTIBYTE {
+                         true => address | T::MULTIBYTE,
+                         false => address,
+                    };
+                    self.interface.read(address, buffer) $(.$await)? .map_err(Error::Interface)
+               }
+
+               /// Write a byte to the given register.
+               $($async)? fn write_register(&mut self, address: u8, value: u8) -> Result<(), Error<E>> {
+                    self.interface.write(address, value) $(.$await)? .map_err(Error::Interface)
+               }
+
+               /// Clear selected bits using a bitmask
+               $($async)? fn clear_register_bit_flag(&mut self, address: u8, bitmask: u8) -> Result<(), Error<E>> {
+                    let payload: u8 = self.read_register(address) $(.$await)? ? & !bitmask;
+                    self.write_register(address, payload) $(.$await)?
+               }
+
+               /// Set selected bits using a bitmask
+               $($async)? fn set_register_bit_flag(&mut self, address: u8, bitmask: u8) -> Result<(), Error<E>> {
+                    let payload: u8 = self.read_register(address) $(.$await)? ? | bitmask;
+                    self.write_register(address, payload) $(.$await)?
+               }
+
+               /// Check if specific bits are set.
+               $($async)? fn is_register_bit_flag_high(
+                    &mut self,
+                    address: u8,
+                    bitmask: u8,
+               ) -> Result<bool, Error<E>> {
+                    let data = self.read_register(address) $(.$await)? ?;
+                    Ok((data & bitmask) != 0)
+               }
+
+               /// Wait until self-clearing bits are cleared by the sensor, polling the register
+               /// at most `POLL_LIMIT` times.
+               $($async)? fn wait_for_bit_flag_low(&mut self, address: u8, bitmask: u8) -> Result<(), Error<E>> {
+                    for _ in 0..POLL_LIMIT {
+                         if !self.is_register_bit_flag_high(address, bitmask) $(.$await)? ? {
+                              return Ok(());
+                         }
+                    }
+                    Err(Error::Timeout)
+               }
+          }
+     };
 }
- 
 
+impl_registers!(LPS2X, Interface; ;);
+#[cfg(feature = "async")]
+impl_registers!(LPS2XAsync<P>, AsyncInterface; async; await);
+  
 /// INT_DRDY pin configuration. (Refer to datasheets)
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy)]
@@ -286,6 +315,17 @@ impl INT_DRDY {
      pub fn value(self) -> u8 {
         self as u8 // no need to shift, bits 0:1
      }
+
+     // LPS22DF and LPS28DFW have no data signal selection
+     #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps22hh", feature = "lps25hb"))]
+     pub(crate) fn from_value(value: u8) -> Self {
+        match value & 0b11 {
+            0b00 => INT_DRDY::DataSignal,
+            0b01 => INT_DRDY::P_high,
+            0b10 => INT_DRDY::P_low,
+            _ => INT_DRDY::P_low_or_high,
+        }
+     }
 }
 
 // Interrupt active setting for the INT_DRDY pin: active high (default) or active low
@@ -300,11 +340,17 @@ pub enum INT_ACTIVE {
 
 impl INT_ACTIVE {
     pub fn status(self) -> bool {
-        let status = match self {
+        match self {
             INT_ACTIVE::High => false,
             INT_ACTIVE::Low => true,
-        };
-        status
+        }
+    }
+
+    pub(crate) fn from_status(status: bool) -> Self {
+        match status {
+            false => INT_ACTIVE::High,
+            true => INT_ACTIVE::Low,
+        }
     }
 }
 
@@ -320,11 +366,17 @@ pub enum INT_PIN {
 
 impl INT_PIN {
     pub fn status(self) -> bool {
-        let status = match self {
+        match self {
             INT_PIN::PushPull => false,
             INT_PIN::OpenDrain => true,
-        };
-        status
+        }
+    }
+
+    pub(crate) fn from_status(status: bool) -> Self {
+        match status {
+            false => INT_PIN::PushPull,
+            true => INT_PIN::OpenDrain,
+        }
     }
 }
 
@@ -340,11 +392,17 @@ pub enum FLAG {
 
 impl FLAG {
     pub fn status(self) -> bool {
-        let status = match self {
+        match self {
             FLAG::Disabled => false,
             FLAG::Enabled => true,
-        };
-        status
+        }
+    }
+
+    pub(crate) fn from_status(status: bool) -> Self {
+        match status {
+            false => FLAG::Disabled,
+            true => FLAG::Enabled,
+        }
     }
 }
 
@@ -360,57 +418,16 @@ pub enum FIFO_ON {
 
 impl FIFO_ON {
     pub fn status(self) -> bool {
-        let status = match self {
+        match self {
             FIFO_ON::Disabled => false,
             FIFO_ON::Enabled => true,
-        };
-        status
+        }
     }
-}
-
-
-
-/// Temperature resolution configuration, number of internal average(Refer to Table 18)
-#[cfg(feature = "lps25hb")]
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy)]
-pub enum TEMP_RES {
-    /// Nr. internal average 8
-    _8 = 0b00,
-    /// Nr. internal average 16
-    _16 = 0b01,
-    /// Nr. internal average 32
-    _32 = 0b10,
-    /// Nr. internal average 64
-    _64 = 0b11,
-}
 
-#[cfg(feature = "lps25hb")]
-impl TEMP_RES {
-    pub fn value(self) -> u8 {
-        (self as u8) << 2 // shifted into the right position, can be used directly
+    pub(crate) fn from_status(status: bool) -> Self {
+        match status {
+            false => FIFO_ON::Disabled,
+            true => FIFO_ON::Enabled,
+        }
     }
 }
-
-/// Pressure resolution configuration, number of internal average(Refer to Table 19)
-#[cfg(feature = "lps25hb")]
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy)]
-pub enum PRESS_RES {
-    /// Nr. internal average 8
-    _8 = 0b00,
-    /// Nr. internal average 32
-    _32 = 0b01,
-    /// Nr. internal average 128
-    _128 = 0b10,
-    /// Nr. internal average 512
-    _512 = 0b11,
-}
-
-#[cfg(feature = "lps25hb")]
-impl PRESS_RES {
-    pub fn value(self) -> u8 {
-        self as u8 // no need to shift
-    }
-}
-
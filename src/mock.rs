@@ -0,0 +1,133 @@
+//! Register file standing in for the sensor in the unit tests
+
+// the tests using each helper depend on the sensor models enabled
+#![allow(dead_code)]
+
+extern crate std;
+
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use super::*;
+use embedded_hal::delay::DelayNs;
+
+/// Error returned by the register file when a bus error is injected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BusError;
+
+/// Delay returning immediately, counting the nanoseconds requested
+#[derive(Debug, Default)]
+pub(crate) struct NoDelay {
+     pub elapsed_ns: u64,
+}
+
+impl DelayNs for NoDelay {
+     fn delay_ns(&mut self, ns: u32) {
+          self.elapsed_ns += ns as u64;
+     }
+}
+
+/// Registers of the sensor, with scripted reads, a FIFO, and a log of the transactions
+pub(crate) struct RegisterFile {
+     pub registers: [u8; 128],
+     /// Values returned by the next reads of a register, before its content
+     pub scripts: Vec<(u8, VecDeque<u8>)>,
+     /// Registers cleared by reading them (e.g. INT_SOURCE)
+     pub clear_on_read: Vec<u8>,
+     /// First FIFO output register: reading it pops a sample while the FIFO is not empty
+     pub fifo_data: u8,
+     /// Samples stored in the FIFO, the oldest first
+     pub fifo: VecDeque<Vec<u8>>,
+     /// Reads (first register, number of bytes), in order
+     pub reads: Vec<(u8, usize)>,
+     /// Writes (register, value), in order
+     pub writes: Vec<(u8, u8)>,
+     /// Number of transactions before the bus fails, `None` if it never fails
+     pub fail_after: Option<usize>,
+}
+
+impl RegisterFile {
+     pub fn new() -> Self {
+          RegisterFile {
+               registers: [0u8; 128],
+               scripts: Vec::new(),
+               clear_on_read: Vec::new(),
+               fifo_data: 0xFF,
+               fifo: VecDeque::new(),
+               reads: Vec::new(),
+               writes: Vec::new(),
+               fail_after: None,
+          }
+     }
+
+     /// Driver for the given sensor model in the mode `M`, without writing to the registers
+     pub fn driver<C: Variant, M: PowerMode>(self, variant: C) -> LPS2X<Self, C, M> {
+          LPS2X::new(self, variant).into_mode()
+     }
+
+     /// Return `values` on the next reads of the register, then its content
+     pub fn script(&mut self, address: u8, values: &[u8]) {
+          self.scripts.push((address, values.iter().copied().collect()));
+     }
+
+     /// Store samples in the FIFO read from `address`
+     pub fn fill_fifo(&mut self, address: u8, samples: &[&[u8]]) {
+          self.fifo_data = address;
+          self.fifo.extend(samples.iter().map(|sample| sample.to_vec()));
+     }
+
+     /// Values written to the register, in order
+     pub fn written(&self, address: u8) -> Vec<u8> {
+          self.writes.iter().filter(|(addr, _)| *addr == address).map(|(_, value)| *value).collect()
+     }
+
+     fn transaction(&mut self) -> Result<(), BusError> {
+          match self.fail_after {
+               Some(0) => Err(BusError),
+               Some(count) => {
+                    self.fail_after = Some(count - 1);
+                    Ok(())
+               }
+               None => Ok(()),
+          }
+     }
+
+     fn read_byte(&mut self, address: u8) -> u8 {
+          if let Some((_, values)) = self.scripts.iter_mut().find(|(addr, values)| *addr == address && !values.is_empty()) {
+               return values.pop_front().unwrap_or_default();
+          }
+          let value = self.registers[address as usize];
+          if self.clear_on_read.contains(&address) {
+               self.registers[address as usize] = 0;
+          }
+          value
+     }
+}
+
+impl Interface for RegisterFile {
+     type Error = BusError;
+     const MULTIBYTE: u8 = 0x80;
+
+     fn write(&mut self, addr: u8, value: u8) -> Result<(), BusError> {
+          self.transaction()?;
+          self.registers[(addr & 0x7F) as usize] = value;
+          self.writes.push((addr & 0x7F, value));
+          Ok(())
+     }
+
+     fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), BusError> {
+          self.transaction()?;
+          let addr = addr & 0x7F;
+          self.reads.push((addr, buffer.len()));
+          if addr == self.fifo_data {
+               if let Some(sample) = self.fifo.pop_front() {
+                    buffer.copy_from_slice(&sample[..buffer.len()]);
+                    return Ok(());
+               }
+          }
+          for (offset, byte) in buffer.iter_mut().enumerate() {
+               *byte = self.read_byte(addr + offset as u8);
+          }
+          Ok(())
+     }
+}
@@ -0,0 +1,147 @@
+//! Power modes of the sensor, encoded in the type of the driver
+//!
+//! The driver starts in `PowerDown` and moves between the modes with the `into_*()` functions
+//! (or with `init()`), so that functions that only make sense in some modes are available
+//! in those modes only:
+//! - `PowerDown`: no measurements, settings that require power-down (e.g. low current mode on LPS22HB)
+//! - `OneShot`: the sensor is powered, a measurement is started by `one_shot()`
+//! - `Continuous`: the sensor measures at the selected output data rate, see `set_datarate()`
+//!
+//! If a transition fails, the driver is returned with the error, still in its previous mode
+//! (the registers may be partly written), so that it can be retried or destroyed: `Err((driver, error))`.
+//! The `?` operator converts this into the `Error` alone, dropping the driver.
+
+use super::*;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Power-down mode: output data rate set to 0 (and sensor turned off on LPS25HB)
+#[derive(Debug)]
+pub struct PowerDown;
+
+/// One-shot mode: output data rate set to 0, sensor powered (LPS25HB),
+/// a new measurement is triggered by `one_shot()`
+#[derive(Debug)]
+pub struct OneShot;
+
+/// Continuous mode: the sensor measures at the selected output data rate
+#[derive(Debug)]
+pub struct Continuous;
+
+impl private::Sealed for PowerDown {}
+impl private::Sealed for OneShot {}
+impl private::Sealed for Continuous {}
+
+/// Power mode of the sensor. This trait is sealed: it is implemented by the types of this module only.
+pub trait PowerMode: private::Sealed {
+    /// The sensor is turned on (PD bit set on LPS25HB)
+    const POWERED: bool;
+    /// The output data rate is not 0
+    const CONTINUOUS: bool;
+}
+
+impl PowerMode for PowerDown {
+    const POWERED: bool = false;
+    const CONTINUOUS: bool = false;
+}
+
+impl PowerMode for OneShot {
+    const POWERED: bool = true;
+    const CONTINUOUS: bool = false;
+}
+
+impl PowerMode for Continuous {
+    const POWERED: bool = true;
+    const CONTINUOUS: bool = true;
+}
+
+/// Power modes in which the output registers are updated and can be read
+pub trait Powered: PowerMode {}
+
+impl Powered for OneShot {}
+impl Powered for Continuous {}
+
+/// Check that the content of CTRL_REG1 matches the mode.
+/// Models without a power down bit are ready for one-shot measurements in power-down mode.
+pub(crate) fn mode_matches<C: Variant, M: PowerMode>(ctrl_reg1: u8) -> bool {
+    let continuous = ctrl_reg1 & C::ODR_MASK != 0;
+    let powered = ctrl_reg1 & C::PD != 0;
+    continuous == M::CONTINUOUS && (C::PD == 0 || powered == M::POWERED)
+}
+
+impl<T, C, MODE, E> LPS2X<T, C, MODE>
+where
+     T: Interface<Error = E>,
+     C: Variant,
+     MODE: PowerMode,
+{
+     /// Change the type of the driver after the mode of the sensor has been changed.
+     pub(crate) fn into_mode<M: PowerMode>(self) -> LPS2X<T, C, M> {
+          LPS2X {
+               interface: self.interface,
+               press_sensitivity: self.press_sensitivity,
+               autozero: self.autozero,
+               fifo_use: self.fifo_use,
+               variant: PhantomData,
+               mode: PhantomData,
+          }
+     }
+}
+
+macro_rules! impl_mode {
+     ($driver:ident $(<$pin:ident>)?, $interface:ident; $($async:ident)?; $($await:ident)?) => {
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               MODE: PowerMode,
+          {
+               /// Write the output data rate and power-down bits of CTRL_REG1, keeping the other settings
+               $($async)? fn write_mode(&mut self, odr: u8, power: u8) -> Result<(), Error<E>> {
+                    let mut payload = self.read_register(C::CTRL_REG1) $(.$await)? ?;
+                    payload &= !(C::ODR_MASK | C::PD);
+                    payload |= odr | power;
+                    self.fifo_use = self.fifo_use.odr_changed();
+                    self.write_register(C::CTRL_REG1, payload) $(.$await)?
+               }
+
+               /// Enter power-down mode: set the output data rate to 0 (and turn the sensor off on LPS25HB).
+               /// Returns the driver with the error if the mode can't be changed.
+               pub $($async)? fn into_power_down(mut self) -> Result<$driver<T, C, PowerDown $(, $pin)?>, (Self, Error<E>)> {
+                    match self.write_mode(0, 0) $(.$await)? {
+                         Ok(()) => Ok(self.into_mode()),
+                         Err(error) => Err((self, error)),
+                    }
+               }
+
+               /// Enter one-shot mode: set the output data rate to 0 (and turn the sensor on on LPS25HB).
+               /// Returns the driver with the error if the mode can't be changed.
+               pub $($async)? fn into_one_shot(mut self) -> Result<$driver<T, C, OneShot $(, $pin)?>, (Self, Error<E>)> {
+                    match self.write_mode(0, C::PD) $(.$await)? {
+                         Ok(()) => Ok(self.into_mode()),
+                         Err(error) => Err((self, error)),
+                    }
+               }
+
+               /// Enter continuous mode with the given output data rate (turning the sensor on on LPS25HB).
+               /// Returns the driver with `Error::InvalidConfig` if the output data rate is `ODR::OneShot`,
+               /// or with the bus error if the mode can't be changed.
+               pub $($async)? fn into_continuous(mut self, odr: C::ODR) -> Result<$driver<T, C, Continuous $(, $pin)?>, (Self, Error<E>)> {
+                    let odr = C::odr_value(odr);
+                    if odr == 0 {
+                         return Err((self, Error::InvalidConfig));
+                    }
+                    match self.write_mode(odr, C::PD) $(.$await)? {
+                         Ok(()) => Ok(self.into_mode()),
+                         Err(error) => Err((self, error)),
+                    }
+               }
+          }
+     };
+}
+
+impl_mode!(LPS2X, Interface; ;);
+#[cfg(feature = "async")]
+impl_mode!(LPS2XAsync<P>, AsyncInterface; async; await);
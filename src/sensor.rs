@@ -1,162 +1,334 @@
 //! Functions related to sensor measurements: reading value or status, setting offset and reference
  
 use super::*;
+use mode::{OneShot, Powered};
+use units::{Pressure, Temperature};
+use convert::*;
+use compensation::TempCompensation;
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
  
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 /// Contents of the STATUS register (pressure and temperature overrun and data availability flags)
-
 pub struct DataStatus {
      pub press_available: bool,
      pub temp_available: bool,
      pub press_overrun: bool,
      pub temp_overrun: bool,
 }
- 
 
-impl<T, E> LPS2X<T>
-where
-     T: Interface<Error = E>,
-{
-     /// Read the device ID ("who am I")
-     pub fn get_device_id(&mut self) -> Result<u8, T::Error> {
-         let mut data = [0u8; 1];
-         self.interface.read(Registers::WHO_AM_I.addr(), &mut data)?;
-         let whoami = data[0];
-         Ok(whoami)
+impl DataStatus {
+     /// Decode the content of the STATUS register
+     pub(crate) fn from_register<C: Variant>(reg_value: u8) -> Self {
+          DataStatus {
+               // Is new pressure data available?
+               press_available: reg_value & C::P_DA != 0,
+               // Is new temperature data available?
+               temp_available: reg_value & C::T_DA != 0,
+               // Has new pressure data overwritten the previous one?
+               press_overrun: reg_value & C::P_OR != 0,
+               // Has new temperature data overwritten the previous one?
+               temp_overrun: reg_value & C::T_OR != 0,
+          }
      }
- 
+}
 
-     // FOR THESE FUNCTIONS TO WORK CORECTLY ON LPS25HB, THE MULTIBYTE HAS TO BE ENABLED DIRECTLY IN THE I2C INTERFACE
-
-     /// Calculated pressure reading in hPa
-     pub fn read_pressure(&mut self) -> Result<f32, T::Error> {
-          let mut data = [0u8; 3];
-          self.interface.read(
-          Registers::PRESS_OUT_XL.addr(),
-          // Registers::PRESS_OUT_XL.addr() | Bitmasks::MULTIBYTE,
-          &mut data,
-          )?;
-          let p: i32 = (data[2] as i32) << 16 | (data[1] as i32) << 8 | (data[0] as i32);
-          let pressure = (p as f32) / PRESS_SCALE; // no need to take care of negative values
-          Ok(pressure)
-     }
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Pressure and temperature read from the same dataset, with the STATUS flags read before them
+pub struct Measurement {
+     pub pressure: Pressure,
+     pub temperature: Temperature,
+     pub status: DataStatus,
+}
 
-     pub fn read_temperature(&mut self) -> Result<f32, T::Error> {
-         let mut data = [0u8; 2];
-         self.interface.read(
-               Registers::TEMP_OUT_L.addr(),
-               // Registers::TEMP_OUT_L.addr() | Bitmasks::MULTIBYTE,
-               &mut data,
-         )?;
-         let t: i16 = (data[1] as i16) << 8 | (data[0] as i16);
-
-         #[cfg(feature = "lps22hb")]
-         let temperature = (t as f32) / TEMP_SCALE;
-         #[cfg(feature = "lps25hb")]
-         let temperature = (t as f32) / TEMP_SCALE + TEMP_OFFSET;
-      
-         Ok(temperature)
-     }
- 
-     /// Calculated reference pressure reading in hPa
-     pub fn read_reference_pressure(&mut self) -> Result<f32, T::Error> {
-          let mut data = [0u8; 3];
-          self.interface.read(Registers::REF_P_XL.addr(), &mut data)?;
-          let p: i32 = (data[2] as i32) << 16 | (data[1] as i32) << 8 | (data[0] as i32);
-          let pressure: f32 = (p as f32) / PRESS_SCALE;
-          Ok(pressure)
-     }
+macro_rules! impl_sensor {
+     ($driver:ident $(<$pin:ident>)?, $interface:ident, $delay:ident, $measure:ident; $($async:ident)?; $($await:ident)?) => {
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               MODE: PowerMode,
+          {
+               /// Read the device ID ("who am I")
+               pub $($async)? fn get_device_id(&mut self) -> Result<u8, Error<E>> {
+                    let whoami = self.read_register(C::WHO_AM_I) $(.$await)? ?;
+                    Ok(whoami)
+               }
 
-     /// Read pressure offset value, 16-bit data that can be used to implement One-Point Calibration (OPC) after soldering.
-     pub fn read_pressure_offset(&mut self) -> Result<i16, T::Error> {
-          let mut data = [0u8; 2];
-          self.interface.read(Registers::RPDS_L.addr(), &mut data)?;
-          let o: i16 = (data[1] as i16) << 8 | (data[0] as i16);
-          Ok(o)
-     }
+               /// Pressure sensitivity in LSB/hPa used to convert the pressure counts
+               /// (4096, or 2048 on LPS28DFW in 4060 hPa full-scale mode)
+               pub fn pressure_sensitivity(&self) -> u16 {
+                    self.press_sensitivity
+               }
 
-     /// Read threshold value for pressure interrupt generation
-     pub fn read_threshold(&mut self) -> Result<i16, T::Error> {
-          let mut data = [0u8; 2];
-          self.interface.read(Registers::THS_P_L.addr(), &mut data)?;
-          let o: i16 = (data[1] as i16) << 8 | (data[0] as i16);
-          Ok(o)
-     }
+               /// Reference pressure counts (REF_P), in the same scale as the pressure counts
+               pub $($async)? fn read_reference_pressure_raw(&mut self) -> Result<i32, Error<E>> {
+                    let mut data = [0u8; 3];
+                    // the least significant byte is missing if REF_P has 16 bits only
+                    self.read_registers(C::REF_P, &mut data[3 - C::REF_P_LEN..]) $(.$await)? ?;
+                    Ok(pressure_counts(data))
+               }
 
+               /// Calculated reference pressure reading
+               pub $($async)? fn read_reference_pressure(&mut self) -> Result<Pressure, Error<E>> {
+                    let counts = self.read_reference_pressure_raw() $(.$await)? ?;
+                    Ok(pressure_from_counts(counts, self.press_sensitivity))
+               }
 
- 
-    /// Set the pressure offset value (VALUE IN hPA!)
+               /// Read pressure offset value, 16-bit data that can be used to implement One-Point Calibration (OPC) after soldering.
+               pub $($async)? fn read_pressure_offset(&mut self) -> Result<Pressure, Error<E>> {
+                    let mut data = [0u8; 2];
+                    self.read_registers(C::RPDS_L, &mut data) $(.$await)? ?;
+                    Ok(pressure_offset_from_bytes(data, self.press_sensitivity))
+               }
 
-     /// Set threshold value for pressure interrupt generation (VALUE IN hPA!)
-     pub fn set_threshold(&mut self, threshold: u16) -> Result<(), T::Error> {
-          let mut payload = [0u8; 2];
-          // The value is expressed as unsigned number: Interrupt threshold(hPA) = (THS_P)/16.
-          let threshold = threshold * 16;
- 
-          payload[0] = (threshold & 0xff) as u8; // lower byte
-          payload[1] = (threshold >> 8) as u8; // upper byte
- 
-          self.interface.write(Registers::THS_P_L.addr(), payload[0])?;
-          self.interface.write(Registers::THS_P_H.addr(), payload[1])?;
- 
-          Ok(())
-     }
+               /// Read threshold value for pressure interrupt generation
+               pub $($async)? fn read_threshold(&mut self) -> Result<Pressure, Error<E>> {
+                    let mut data = [0u8; 2];
+                    self.read_registers(C::THS_P_L, &mut data) $(.$await)? ?;
+                    Ok(threshold_from_bytes(data, self.press_sensitivity))
+               }
 
-     /// Set the pressure offset value (VALUE IN hPA!)
-     pub fn set_pressure_offset(&mut self, offset: u16) -> Result<(), T::Error> {
-          let mut payload = [0u8; 2];
-          let offset = offset * 16;
+               /// Set threshold value for pressure interrupt generation
+               /// Returns `Error::OutOfRange` if the value is negative or doesn't fit in the THS_P registers.
+               pub $($async)? fn set_threshold(&mut self, threshold: Pressure) -> Result<(), Error<E>> {
+                    // The value is expressed as unsigned number: Interrupt threshold(hPA) = (THS_P)/16
+                    // (THS_P/8 on LPS28DFW in 4060 hPa full-scale mode).
+                    let payload = threshold_to_bytes(threshold, self.press_sensitivity).ok_or(Error::OutOfRange)?;
 
-          payload[0] = (offset & 0xff) as u8; // lower byte
-          payload[1] = (offset >> 8) as u8; // upper byte
+                    self.write_register(C::THS_P_L, payload[0]) $(.$await)? ?;
+                    self.write_register(C::THS_P_H, payload[1]) $(.$await)? ?;
 
-          self.interface.write(Registers::RPDS_L.addr(), payload[0])?;
-          self.interface.write(Registers::RPDS_H.addr(), payload[1])?;
+                    Ok(())
+               }
 
-          Ok(())
-     }
- 
-     /// Get all the flags from the STATUS_REG register
-     pub fn get_data_status(&mut self) -> Result<DataStatus, T::Error> {         
-          let reg_value = self.read_register(Registers::STATUS)?;
-         
-          // IN LPS25HB THE NAME OF THE REGISTER IS STATUS_REG
-          // let reg_value = self.read_register(Registers::STATUS_REG)?;
- 
-          let status = DataStatus {
-               /// Is new pressure data available?
-               press_available: match reg_value & Bitmasks::P_DA {
-                    0 => false,
-                    _ => true,
-               },
-               /// Is new temperature data available?
-               temp_available: match reg_value & Bitmasks::T_DA {
-                    0 => false,
-                    _ => true,
-               },
-               
-               /// Has new pressure data overwritten the previous one?   
-               press_overrun: match reg_value & Bitmasks::P_OR {
-                    0 => false,
-                    _ => true,
-               },
-               /// Has new temperature data overwritten the previous one?
-               temp_overrun: match reg_value & Bitmasks::T_OR {
-                    0 => false,
-                    _ => true,
-               },
-          };
-          Ok(status)
-     }
- 
-    /// Triggers the one-shot mode, and a new acquisition starts when it is required.
-    /// Enabling this mode is possible only if the device was previously in power-down/one-shot mode.
-    /// Once the acquisition is completed and the output registers updated,
-    /// the device automatically enters in power-down mode. ONE_SHOT bit self-clears itself,
-    /// the new data are available in the output registers and the STATUS bits are updated.    
-     pub fn one_shot(&mut self) -> Result<(), T::Error> {          
-          self.set_datarate(ODR::OneShot)?; // make sure that OneShot/Power down mode is enabled
-          self.set_register_bit_flag(Registers::CTRL_REG2, Bitmasks::ONE_SHOT)?;
-         Ok(())
+               /// Set the pressure offset value (signed)
+               /// Returns `Error::OutOfRange` if the value doesn't fit in the RPDS registers.
+               pub $($async)? fn set_pressure_offset(&mut self, offset: Pressure) -> Result<(), Error<E>> {
+                    let payload = pressure_offset_to_bytes(offset, self.press_sensitivity).ok_or(Error::OutOfRange)?;
+
+                    self.write_register(C::RPDS_L, payload[0]) $(.$await)? ?;
+                    self.write_register(C::RPDS_H, payload[1]) $(.$await)? ?;
+
+                    Ok(())
+               }
+          }
+
+          #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw", feature = "lps25hb"))]
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               MODE: PowerMode,
+          {
+               /// Write the reference pressure in the REF_P registers (read-write on LPS22HB, LPS25HB, LPS33HW and LPS35HW)
+               $($async)? fn write_reference_pressure(&mut self, reference: Pressure) -> Result<(), Error<E>> {
+                    let payload = reference_pressure_to_bytes::<C>(reference, self.press_sensitivity).ok_or(Error::OutOfRange)?;
+                    for (offset, value) in payload[3 - C::REF_P_LEN..].iter().enumerate() {
+                         self.write_register(C::REF_P + offset as u8, *value) $(.$await)? ?;
+                    }
+                    Ok(())
+               }
+          }
+
+          #[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant<ODR = variant::lps22hb::ODR>,
+               MODE: PowerMode,
+          {
+               /// Set the reference pressure (REF_P) used by the autozero and AUTORIFP functions
+               /// and by the differential pressure interrupts (measured pressure - REF_P compared with the threshold).
+               /// Enabling autozero or AUTORIFP copies the current pressure into REF_P: set the chosen baseline afterwards.
+               /// Returns `Error::OutOfRange` if the value doesn't fit in the REF_P registers.
+               pub $($async)? fn set_reference_pressure(&mut self, reference: Pressure) -> Result<(), Error<E>> {
+                    self.write_reference_pressure(reference) $(.$await)?
+               }
+          }
+
+          #[cfg(feature = "lps25hb")]
+          impl<T, MODE, $($pin,)? E> $driver<T, LPS25HB, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               MODE: PowerMode,
+          {
+               /// Set the reference pressure (REF_P) used by the autozero function
+               /// and by the differential pressure interrupts (measured pressure - REF_P compared with the threshold).
+               /// Enabling autozero copies the current pressure into REF_P: set the chosen baseline afterwards.
+               /// Returns `Error::OutOfRange` if the value doesn't fit in the REF_P registers.
+               pub $($async)? fn set_reference_pressure(&mut self, reference: Pressure) -> Result<(), Error<E>> {
+                    self.write_reference_pressure(reference) $(.$await)?
+               }
+          }
+
+          impl<T, C, MODE, $($pin,)? E> $driver<T, C, MODE $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+               MODE: Powered,
+          {
+               /// Calculated pressure reading, as given by the sensor: relative to REF_P if autozero is enabled,
+               /// see `read_absolute_pressure()` and `read_differential_pressure()`
+               pub $($async)? fn read_pressure(&mut self) -> Result<Pressure, Error<E>> {
+                    let counts = self.read_pressure_raw() $(.$await)? ?;
+                    Ok(pressure_from_counts(counts, self.press_sensitivity))
+               }
+
+               /// Absolute pressure reading. If autozero is enabled, the sensor output is relative
+               /// to REF_P and the reference pressure is read and added back.
+               pub $($async)? fn read_absolute_pressure(&mut self) -> Result<Pressure, Error<E>> {
+                    let counts = match self.autozero {
+                         true => self.read_pressure_raw() $(.$await)? ? + self.read_reference_pressure_raw() $(.$await)? ?,
+                         false => self.read_pressure_raw() $(.$await)? ?,
+                    };
+                    Ok(pressure_from_counts(counts, self.press_sensitivity))
+               }
+
+               /// Differential pressure reading (measured pressure - REF_P). If autozero is disabled
+               /// (e.g. with AUTORIFP, or a reference set with `set_reference_pressure()`), the sensor output
+               /// is absolute and the reference pressure is read and subtracted.
+               pub $($async)? fn read_differential_pressure(&mut self) -> Result<Pressure, Error<E>> {
+                    let counts = match self.autozero {
+                         true => self.read_pressure_raw() $(.$await)? ?,
+                         false => self.read_pressure_raw() $(.$await)? ? - self.read_reference_pressure_raw() $(.$await)? ?,
+                    };
+                    Ok(pressure_from_counts(counts, self.press_sensitivity))
+               }
+
+               /// Calculated temperature reading
+               pub $($async)? fn read_temperature(&mut self) -> Result<Temperature, Error<E>> {
+                    let counts = self.read_temperature_raw() $(.$await)? ?;
+                    Ok(temperature_from_counts::<C>(counts))
+               }
+
+               /// Raw pressure reading: sign-extended counts, see `pressure_sensitivity()` for the scale
+               pub $($async)? fn read_pressure_raw(&mut self) -> Result<i32, Error<E>> {
+                    let mut data = [0u8; 3];
+                    self.read_registers(C::PRESS_OUT_XL, &mut data) $(.$await)? ?;
+                    Ok(pressure_counts(data))
+               }
+
+               /// Raw temperature reading: counts, divided by `C::TEMP_SCALE` (plus an offset on LPS25HB) in degrees Celsius
+               pub $($async)? fn read_temperature_raw(&mut self) -> Result<i16, Error<E>> {
+                    let mut data = [0u8; 2];
+                    self.read_registers(C::TEMP_OUT_L, &mut data) $(.$await)? ?;
+                    Ok(temperature_counts(data))
+               }
+
+               /// Pressure reading in hundredths of a pascal (hPa x 10000), computed without floating point
+               pub $($async)? fn read_pressure_centipascal(&mut self) -> Result<i32, Error<E>> {
+                    Ok(self.read_pressure() $(.$await)? ?.as_centipascal())
+               }
+
+               /// Temperature reading in hundredths of a degree Celsius, computed without floating point
+               pub $($async)? fn read_temperature_centidegrees(&mut self) -> Result<i16, Error<E>> {
+                    let counts = self.read_temperature_raw() $(.$await)? ?;
+                    Ok(temperature_centidegrees::<C>(counts))
+               }
+
+               /// Read STATUS, PRESS_OUT and TEMP_OUT in a single bus transaction, so pressure, temperature
+               /// and the data available/overrun flags belong together even with block data update disabled.
+               /// The STATUS register is followed by the output registers on all the models; register address
+               /// auto-increment must be enabled (register default, see `address_incrementing()`).
+               pub $($async)? fn read_measurement(&mut self) -> Result<Measurement, Error<E>> {
+                    let mut data = [0u8; 6];
+                    self.read_registers(C::STATUS, &mut data) $(.$await)? ?;
+                    Ok(measurement_from_bytes::<C>(data, self.press_sensitivity))
+               }
+
+               /// Read pressure and temperature in a single bus transaction, see `read_measurement()`,
+               /// and compensate the pressure for the temperature-dependent offset
+               pub $($async)? fn read_compensated_measurement(&mut self, compensation: &TempCompensation) -> Result<Measurement, Error<E>> {
+                    let measurement = self.read_measurement() $(.$await)? ?;
+                    Ok(compensation.compensate_measurement(measurement))
+               }
+
+               /// Get all the flags from the STATUS_REG register
+               pub $($async)? fn get_data_status(&mut self) -> Result<DataStatus, Error<E>> {
+                    let reg_value = self.read_register(C::STATUS) $(.$await)? ?;
+                    Ok(DataStatus::from_register::<C>(reg_value))
+               }
+          }
+
+          impl<T, C, $($pin,)? E> $driver<T, C, OneShot $(, $pin)?>
+          where
+               T: $interface<Error = E>,
+               C: Variant,
+          {
+               /// Triggers the one-shot mode, and a new acquisition starts when it is required.
+               /// Once the acquisition is completed and the output registers updated,
+               /// the device automatically enters in power-down mode. ONE_SHOT bit self-clears itself,
+               /// the new data are available in the output registers and the STATUS bits are updated.
+               pub $($async)? fn one_shot(&mut self) -> Result<(), Error<E>> {
+                    self.set_register_bit_flag(C::CTRL_REG2, C::ONE_SHOT) $(.$await)? ?;
+                    Ok(())
+               }
+
+               /// Trigger a one-shot measurement and wait for it, polling the self-clearing ONE_SHOT bit
+               /// every millisecond, then read pressure and temperature.
+               /// Returns `Error::Timeout` if the data are not available after `timeout_ms` milliseconds.
+               pub $($async)? fn $measure<D: $delay>(&mut self, delay: &mut D, timeout_ms: u16) -> Result<Measurement, Error<E>> {
+                    self.one_shot() $(.$await)? ?;
+                    let mut elapsed: u16 = 0;
+                    while self.is_register_bit_flag_high(C::CTRL_REG2, C::ONE_SHOT) $(.$await)? ? {
+                         if elapsed >= timeout_ms {
+                              return Err(Error::Timeout);
+                         }
+                         delay.delay_ms(1) $(.$await)?;
+                         elapsed += 1;
+                    }
+                    self.read_measurement() $(.$await)?
+               }
+
+               /// Start a one-shot measurement, to be completed with `poll_measurement()`.
+               pub $($async)? fn start_measurement(&mut self) -> Result<(), Error<E>> {
+                    self.one_shot() $(.$await)?
+               }
+
+               /// Check if the measurement started by `start_measurement()` is complete.
+               /// Returns `nb::Error::WouldBlock` until both pressure and temperature data are available,
+               /// then returns them (read together with the STATUS register, see `read_measurement()`).
+               /// The STATUS register is read alone until then: reading the output registers would clear
+               /// the data available flag of a value ready before the other one.
+               pub $($async)? fn poll_measurement(&mut self) -> nb::Result<Measurement, Error<E>> {
+                    let status = self.get_data_status() $(.$await)? ?;
+                    if !(status.press_available && status.temp_available) {
+                         return Err(nb::Error::WouldBlock);
+                    }
+                    Ok(self.read_measurement() $(.$await)? ?)
+               }
+          }
+     };
+}
+
+impl_sensor!(LPS2X, Interface, DelayNs, measure_blocking; ;);
+#[cfg(feature = "async")]
+impl_sensor!(LPS2XAsync<P>, AsyncInterface, AsyncDelayNs, measure; async; await);
+
+#[cfg(all(test, feature = "lps25hb"))]
+mod tests {
+     use super::*;
+     use mock::RegisterFile;
+
+     #[test]
+     fn poll_measurement_reads_the_outputs_once_both_values_are_available() {
+          let status = <LPS25HB as Variant>::STATUS;
+          let mut registers = RegisterFile::new();
+          registers.script(status, &[0, LPS25HB::P_DA, LPS25HB::P_DA | LPS25HB::T_DA, LPS25HB::P_DA | LPS25HB::T_DA]);
+          // 1000 hPa, 42.5 °C
+          registers.registers[LPS25HB::PRESS_OUT_XL as usize..][..5].copy_from_slice(&[0x00, 0x80, 0x3E, 0x00, 0x00]);
+          let mut sensor: LPS2X<_, _, OneShot> = registers.driver(LPS25HB);
+
+          assert!(matches!(sensor.poll_measurement(), Err(nb::Error::WouldBlock)));
+          assert!(matches!(sensor.poll_measurement(), Err(nb::Error::WouldBlock)));
+          let measurement = sensor.poll_measurement().unwrap();
+          assert_eq!(measurement.pressure.as_centipascal(), 1000 * 10000);
+          assert_eq!(measurement.temperature.as_centidegrees(), 4250);
+          assert!(measurement.status.press_available && measurement.status.temp_available);
+
+          // STATUS alone while waiting, then STATUS and the output registers together
+          assert_eq!(sensor.interface.reads, [(status, 1), (status, 1), (status, 1), (status, 6)]);
      }
 }
@@ -0,0 +1,262 @@
+//! Pressure and temperature quantities returned and accepted by the driver
+//!
+//! Both types hold an integer value (hundredths of a pascal, hundredths of a degree Celsius),
+//! so they can be used on targets without FPU. The conversions to and from other units
+//! as `f32` require the `float` feature.
+//!
+//! ```
+//! use lps2x::units::{Pressure, Temperature};
+//!
+//! let pressure = Pressure::from_centipascal(10_132_500);
+//! assert_eq!(pressure.as_pascal(), 101_325);
+//! assert_eq!(pressure - Pressure::from_pascal(25), Pressure::from_pascal(101_300));
+//!
+//! let temperature = Temperature::from_centidegrees(2500);
+//! assert_eq!(temperature.as_centidegrees(), 2500);
+//! ```
+//!
+//! With the `float` feature:
+//!
+//! ```
+//! # #[cfg(feature = "float")] {
+//! use lps2x::units::{Pressure, Temperature};
+//!
+//! let pressure = Pressure::from_centipascal(10_132_500);
+//! assert!((pressure.as_hpa() - 1013.25).abs() < 0.001);
+//! assert!((pressure.as_atm() - 1.0).abs() < 0.0001);
+//!
+//! let temperature = Temperature::from_celsius(25.0);
+//! assert_eq!(temperature.as_centidegrees(), 2500);
+//! assert!((temperature.as_fahrenheit() - 77.0).abs() < 0.001);
+//! # }
+//! ```
+
+use core::ops::{Add, Neg, Sub};
+
+/// Pascals in one inch of mercury
+#[cfg(feature = "float")]
+const PA_PER_INHG: f32 = 3386.389;
+/// Pascals in one millimetre of mercury
+#[cfg(feature = "float")]
+const PA_PER_MMHG: f32 = 133.322_39;
+/// Pascals in one pound per square inch
+#[cfg(feature = "float")]
+const PA_PER_PSI: f32 = 6894.757;
+/// Pascals in one standard atmosphere
+#[cfg(feature = "float")]
+const PA_PER_ATM: f32 = 101_325.0;
+
+/// Round to the nearest integer, halfway cases away from zero (no `f32::round()` in `core`)
+#[cfg(feature = "float")]
+fn round(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+/// Pressure, stored in hundredths of a pascal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pressure {
+    centipascal: i32,
+}
+
+impl Pressure {
+    /// Pressure from hundredths of a pascal
+    pub const fn from_centipascal(centipascal: i32) -> Self {
+        Pressure { centipascal }
+    }
+
+    /// Pressure from whole pascals
+    pub const fn from_pascal(pascal: i32) -> Self {
+        Pressure { centipascal: pascal * 100 }
+    }
+
+    /// Pressure from whole hectopascals (millibars)
+    pub const fn from_hectopascal(hpa: i32) -> Self {
+        Pressure { centipascal: hpa * 10_000 }
+    }
+
+    /// Pressure in hundredths of a pascal
+    pub const fn as_centipascal(self) -> i32 {
+        self.centipascal
+    }
+
+    /// Pressure in whole pascals (rounded toward zero)
+    pub const fn as_pascal(self) -> i32 {
+        self.centipascal / 100
+    }
+
+    /// Pressure from the counts of the output registers, with the given sensitivity in LSB/hPa
+    /// (rounded toward zero)
+    pub(crate) fn from_counts(counts: i32, sensitivity: u16) -> Self {
+        Pressure {
+            centipascal: (counts as i64 * 10_000 / sensitivity as i64) as i32,
+        }
+    }
+
+    /// Counts of the output registers for this pressure, with the given sensitivity in LSB/hPa
+    /// (rounded toward zero)
+    pub(crate) fn to_counts(self, sensitivity: u16) -> i64 {
+        self.centipascal as i64 * sensitivity as i64 / 10_000
+    }
+}
+
+impl Add for Pressure {
+    type Output = Pressure;
+
+    fn add(self, rhs: Pressure) -> Pressure {
+        Pressure { centipascal: self.centipascal + rhs.centipascal }
+    }
+}
+
+impl Sub for Pressure {
+    type Output = Pressure;
+
+    fn sub(self, rhs: Pressure) -> Pressure {
+        Pressure { centipascal: self.centipascal - rhs.centipascal }
+    }
+}
+
+impl Neg for Pressure {
+    type Output = Pressure;
+
+    fn neg(self) -> Pressure {
+        Pressure { centipascal: -self.centipascal }
+    }
+}
+
+#[cfg(feature = "float")]
+impl Pressure {
+    /// Pressure from pascals
+    pub fn from_pa(pa: f32) -> Self {
+        Pressure { centipascal: round(pa * 100.0) }
+    }
+
+    /// Pressure from hectopascals
+    pub fn from_hpa(hpa: f32) -> Self {
+        Self::from_pa(hpa * 100.0)
+    }
+
+    /// Pressure from kilopascals
+    pub fn from_kpa(kpa: f32) -> Self {
+        Self::from_pa(kpa * 1000.0)
+    }
+
+    /// Pressure from millibars (same as hectopascals)
+    pub fn from_mbar(mbar: f32) -> Self {
+        Self::from_hpa(mbar)
+    }
+
+    /// Pressure from inches of mercury
+    pub fn from_inhg(inhg: f32) -> Self {
+        Self::from_pa(inhg * PA_PER_INHG)
+    }
+
+    /// Pressure from millimetres of mercury
+    pub fn from_mmhg(mmhg: f32) -> Self {
+        Self::from_pa(mmhg * PA_PER_MMHG)
+    }
+
+    /// Pressure from pounds per square inch
+    pub fn from_psi(psi: f32) -> Self {
+        Self::from_pa(psi * PA_PER_PSI)
+    }
+
+    /// Pressure from standard atmospheres
+    pub fn from_atm(atm: f32) -> Self {
+        Self::from_pa(atm * PA_PER_ATM)
+    }
+
+    /// Pressure in pascals
+    pub fn as_pa(self) -> f32 {
+        self.centipascal as f32 / 100.0
+    }
+
+    /// Pressure in hectopascals
+    pub fn as_hpa(self) -> f32 {
+        self.centipascal as f32 / 10_000.0
+    }
+
+    /// Pressure in kilopascals
+    pub fn as_kpa(self) -> f32 {
+        self.centipascal as f32 / 100_000.0
+    }
+
+    /// Pressure in millibars (same as hectopascals)
+    pub fn as_mbar(self) -> f32 {
+        self.as_hpa()
+    }
+
+    /// Pressure in inches of mercury
+    pub fn as_inhg(self) -> f32 {
+        self.as_pa() / PA_PER_INHG
+    }
+
+    /// Pressure in millimetres of mercury
+    pub fn as_mmhg(self) -> f32 {
+        self.as_pa() / PA_PER_MMHG
+    }
+
+    /// Pressure in pounds per square inch
+    pub fn as_psi(self) -> f32 {
+        self.as_pa() / PA_PER_PSI
+    }
+
+    /// Pressure in standard atmospheres
+    pub fn as_atm(self) -> f32 {
+        self.as_pa() / PA_PER_ATM
+    }
+}
+
+/// Temperature, stored in hundredths of a degree Celsius
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Temperature {
+    centidegrees: i32,
+}
+
+impl Temperature {
+    /// Temperature from hundredths of a degree Celsius
+    pub const fn from_centidegrees(centidegrees: i32) -> Self {
+        Temperature { centidegrees }
+    }
+
+    /// Temperature in hundredths of a degree Celsius
+    pub const fn as_centidegrees(self) -> i32 {
+        self.centidegrees
+    }
+}
+
+#[cfg(feature = "float")]
+impl Temperature {
+    /// Temperature from degrees Celsius
+    pub fn from_celsius(celsius: f32) -> Self {
+        Temperature { centidegrees: round(celsius * 100.0) }
+    }
+
+    /// Temperature from degrees Fahrenheit
+    pub fn from_fahrenheit(fahrenheit: f32) -> Self {
+        Self::from_celsius((fahrenheit - 32.0) * 5.0 / 9.0)
+    }
+
+    /// Temperature from kelvins
+    pub fn from_kelvin(kelvin: f32) -> Self {
+        Self::from_celsius(kelvin - 273.15)
+    }
+
+    /// Temperature in degrees Celsius
+    pub fn as_celsius(self) -> f32 {
+        self.centidegrees as f32 / 100.0
+    }
+
+    /// Temperature in degrees Fahrenheit
+    pub fn as_fahrenheit(self) -> f32 {
+        self.as_celsius() * 9.0 / 5.0 + 32.0
+    }
+
+    /// Temperature in kelvins
+    pub fn as_kelvin(self) -> f32 {
+        self.as_celsius() + 273.15
+    }
+}
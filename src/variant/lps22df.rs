@@ -0,0 +1,510 @@
+//! LPS22DF register mapping, bitmasks, output data rates, averaging and FIFO modes
+//!
+//! The LPS28DFW has the same register map (plus the full-scale selection in CTRL_REG2),
+//! the tables and conversions of this module are shared with the `lps28dfw` module.
+
+use super::Variant;
+#[cfg(feature = "lps22df")]
+use super::Sealed;
+use crate::config::{ConfigRegisters, DeviceConfig, SensorConfig};
+use crate::fifo::{FIFOConfig, FIFO_TRIGGER};
+use crate::interrupt::InterruptConfig;
+use crate::{FIFO_ON, FLAG};
+
+/// LPS22DF sensor model
+#[cfg(feature = "lps22df")]
+#[derive(Debug, Clone, Copy)]
+pub struct LPS22DF;
+
+/// Registers
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub enum Registers {
+     /// Interrupt control.
+     INTERRUPT_CFG = 0x0B,
+     /// Pressure threshold low.
+     THS_P_L = 0x0C,
+     /// Pressure threshold high.
+     THS_P_H = 0x0D,
+     /// Interface control.
+     IF_CTRL = 0x0E,
+     /// Who Am I (identifies the chip).
+     WHO_AM_I = 0x0F,
+     /// Control register 1.
+     CTRL_REG1 = 0x10,
+     /// Control register 2.
+     CTRL_REG2 = 0x11,
+     /// Control register 3.
+     CTRL_REG3 = 0x12,
+     /// Control register 4.
+     CTRL_REG4 = 0x13,
+     /// FIFO configuration register.
+     FIFO_CTRL = 0x14,
+     /// FIFO watermark level.
+     FIFO_WTM = 0x15,
+     /// Reference pressure register.
+     REF_P_L = 0x16,
+     /// Reference pressure register.
+     REF_P_H = 0x17,
+     /// Pressure offset register.
+     RPDS_L = 0x1A,
+     /// Pressure offset register.
+     RPDS_H = 0x1B,
+     /// Interrupt configuration.
+     INT_SOURCE = 0x24,
+     /// FIFO status register (stored data level).
+     FIFO_STATUS1 = 0x25,
+     /// FIFO status register (flags).
+     FIFO_STATUS2 = 0x26,
+     /// Status register.
+     STATUS = 0x27,
+     /// Pressure output register.
+     PRESS_OUT_XL = 0x28,
+     /// Pressure output register.
+     PRESS_OUT_L = 0x29,
+     /// Pressure output register.
+     PRESS_OUT_H = 0x2A,
+     /// Temperature output register.
+     TEMP_OUT_L = 0x2B,
+     /// Temperature output register.
+     TEMP_OUT_H = 0x2C,
+     /// FIFO pressure output register.
+     FIFO_DATA_OUT_PRESS_XL = 0x78,
+     /// FIFO pressure output register.
+     FIFO_DATA_OUT_PRESS_L = 0x79,
+     /// FIFO pressure output register.
+     FIFO_DATA_OUT_PRESS_H = 0x7A,
+}
+
+impl Registers {
+     pub fn addr(self) -> u8 {
+         self as u8
+     }
+}
+
+/// Bit masks
+#[allow(non_camel_case_types)]
+pub struct Bitmasks;
+
+#[allow(dead_code)]
+/// LPS22DF Bit masks
+impl Bitmasks {
+    // === INTERRUPT_CFG (0x0B) ===
+    pub (crate) const AUTOREFP: u8 = 0b1000_0000;
+    pub (crate) const RESET_ARP: u8 = 0b0100_0000;
+    pub (crate) const AUTOZERO: u8 = 0b0010_0000;
+    pub (crate) const RESET_AZ: u8 = 0b0001_0000;
+    /// Latch Interrupt Request
+    pub (crate) const LIR: u8 = 0b0000_0100;
+    /// Enable interrupt generation on Low Pressure Event
+    pub (crate) const PLE: u8 = 0b0000_0010;
+    /// Enable interrupt generation on High Pressure Event
+    pub (crate) const PHE: u8 = 0b0000_0001;
+
+    // === IF_CTRL (0x0E) ===
+    pub (crate) const INT_EN_I3C: u8 = 0b1000_0000;
+    /// Disable I2C and I3C interfaces (LPS22DF only)
+    pub (crate) const I2C_I3C_DIS: u8 = 0b0100_0000;
+    /// SPI Interface Mode Selection (LPS22DF only)
+    pub (crate) const SIM: u8 = 0b0010_0000;
+    pub (crate) const SDA_PU_EN: u8 = 0b0001_0000;
+    pub (crate) const SDO_PU_EN: u8 = 0b0000_1000;
+    pub (crate) const INT_PD_DIS: u8 = 0b0000_0100;
+    pub (crate) const CS_PU_DIS: u8 = 0b0000_0010;
+
+    // === CTRL_REG1 (0x10) ===
+    /// Output data rate selection
+    pub (crate) const ODR_MASK: u8 = 0b0111_1000;
+    /// Number of averages selection
+    pub (crate) const AVG_MASK: u8 = 0b0000_0111;
+
+    // === CTRL_REG2 (0x11) ===
+    pub (crate) const BOOT: u8 = 0b1000_0000;
+    /// Full-scale mode selection (LPS28DFW only); must be changed in power-down mode
+    pub (crate) const FS_MODE: u8 = 0b0100_0000;
+    pub (crate) const LPFP_CFG: u8 = 0b0010_0000;
+    /// Low pass filter on pressure data in Continuous mode
+    pub (crate) const EN_LPFP: u8 = 0b0001_0000;
+    /// Block data update
+    pub (crate) const BDU: u8 = 0b0000_1000;
+    pub (crate) const SWRESET: u8 = 0b0000_0100;
+    /// Enable single shot to acquire a new dataset
+    pub (crate) const ONE_SHOT: u8 = 0b0000_0001;
+
+    // === CTRL_REG3 (0x12) ===
+    pub (crate) const INT_H_L: u8 = 0b0000_1000;
+    pub (crate) const PP_OD: u8 = 0b0000_0010;
+    /// Increment address during multiple byte read (I2C/SPI), default 1 (enabled)
+    pub (crate) const IF_ADD_INC: u8 = 0b0000_0001;
+
+    // === CTRL_REG4 (0x13) ===
+    /// Data-ready signal pulsed (instead of latched)
+    pub (crate) const DRDY_PLS: u8 = 0b0100_0000;
+    /// Data-ready signal on INT_DRDY pin
+    pub (crate) const DRDY: u8 = 0b0010_0000;
+    /// Pressure interrupts on INT_DRDY pin
+    pub (crate) const INT_EN: u8 = 0b0001_0000;
+    /// FIFO full flag on INT_DRDY pin
+    pub (crate) const INT_F_FULL: u8 = 0b0000_0100;
+    /// FIFO watermark status on INT_DRDY pin
+    pub (crate) const INT_F_WTM: u8 = 0b0000_0010;
+    /// FIFO overrun status on INT_DRDY pin
+    pub (crate) const INT_F_OVR: u8 = 0b0000_0001;
+
+    // === FIFO_CTRL (0x14) ===
+    /// Stop on FIFO watermark
+    pub (crate) const STOP_ON_WTM: u8 = 0b0000_1000;
+    /// FIFO mode selection (TRIG_MODES and F_MODE bits)
+    pub (crate) const F_MODE_MASK: u8 = 0b0000_0111;
+
+    // === FIFO_WTM (0x15) ===
+    /// FIFO watermark level selection
+    pub (crate) const WTM_MASK: u8 = 0b0111_1111;
+
+    // === INT_SOURCE (0x24) ===
+    /// Reboot phase status (1 - running)
+    pub (crate) const BOOT_ON: u8 = 0b1000_0000;
+    /// Interrupt active
+    pub (crate) const IA: u8 = 0b0000_0100;
+    /// Differential pressure low
+    pub (crate) const PL: u8 = 0b0000_0010;
+    /// Differential pressure high
+    pub (crate) const PH: u8 = 0b0000_0001;
+
+    // === FIFO_STATUS2 (0x26) ===
+    /// FIFO watermark status
+    pub (crate) const FIFO_WTM_IA: u8 = 0b1000_0000;
+    /// FIFO overrun status
+    pub (crate) const FIFO_OVR_IA: u8 = 0b0100_0000;
+    /// FIFO full status
+    pub (crate) const FIFO_FULL_IA: u8 = 0b0010_0000;
+
+    // === STATUS (0x27) ===
+    /// Temperature data overrun
+    pub (crate) const T_OR: u8 = 0b0010_0000;
+    /// Pressure data overrun
+    pub (crate) const P_OR: u8 = 0b0001_0000;
+    /// Temperature data available
+    pub (crate) const T_DA: u8 = 0b0000_0010;
+    /// Pressure data available
+    pub (crate) const P_DA: u8 = 0b0000_0001;
+}
+
+/// Output data rate and power mode selection (ODR). (Refer to Table 18)
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ODR {
+    /// Power-down / One-shot mode enabled
+    #[default]
+    OneShot = 0b0000,
+    /// 1 Hz
+    _1Hz = 0b0001,
+    /// 4 Hz
+    _4Hz = 0b0010,
+    /// 10 Hz
+    _10Hz = 0b0011,
+    /// 25 Hz
+    _25Hz = 0b0100,
+    /// 50 Hz
+    _50Hz = 0b0101,
+    /// 75 Hz
+    _75Hz = 0b0110,
+    /// 100 Hz
+    _100Hz = 0b0111,
+    /// 200 Hz
+    _200Hz = 0b1000,
+}
+
+impl ODR {
+     pub fn value(self) -> u8 {
+        (self as u8) << 3 // shifted into the right position, can be used directly
+     }
+
+     /// Time between two samples in microseconds, `None` in one-shot mode
+     pub fn period_us(self) -> Option<u32> {
+        match self {
+            ODR::OneShot => None,
+            ODR::_1Hz => Some(1_000_000),
+            ODR::_4Hz => Some(250_000),
+            ODR::_10Hz => Some(100_000),
+            ODR::_25Hz => Some(40_000),
+            ODR::_50Hz => Some(20_000),
+            ODR::_75Hz => Some(13_333),
+            ODR::_100Hz => Some(10_000),
+            ODR::_200Hz => Some(5_000),
+        }
+     }
+
+     pub(crate) fn from_value(value: u8) -> Option<Self> {
+        match (value & Bitmasks::ODR_MASK) >> 3 {
+            0b0000 => Some(ODR::OneShot),
+            0b0001 => Some(ODR::_1Hz),
+            0b0010 => Some(ODR::_4Hz),
+            0b0011 => Some(ODR::_10Hz),
+            0b0100 => Some(ODR::_25Hz),
+            0b0101 => Some(ODR::_50Hz),
+            0b0110 => Some(ODR::_75Hz),
+            0b0111 => Some(ODR::_100Hz),
+            0b1000 => Some(ODR::_200Hz),
+            _ => None,
+        }
+     }
+}
+
+/// Number of internal averages of pressure and temperature (AVG). (Refer to Table 19)
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum AVG {
+    /// 4 averages
+    #[default]
+    _4 = 0b000,
+    /// 8 averages
+    _8 = 0b001,
+    /// 16 averages
+    _16 = 0b010,
+    /// 32 averages
+    _32 = 0b011,
+    /// 64 averages
+    _64 = 0b100,
+    /// 128 averages
+    _128 = 0b101,
+    /// 512 averages
+    _512 = 0b111,
+}
+
+impl AVG {
+     pub fn value(self) -> u8 {
+        self as u8 // no need to shift, bits 0:2
+     }
+
+     pub(crate) fn from_value(value: u8) -> Option<Self> {
+        match value & Bitmasks::AVG_MASK {
+            0b000 => Some(AVG::_4),
+            0b001 => Some(AVG::_8),
+            0b010 => Some(AVG::_16),
+            0b011 => Some(AVG::_32),
+            0b100 => Some(AVG::_64),
+            0b101 => Some(AVG::_128),
+            0b111 => Some(AVG::_512),
+            _ => None,
+        }
+     }
+}
+
+/// FIFO mode selection, TRIG_MODES and F_MODE bits. (Refer to Table 23)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FIFO_MODE {
+    /// Bypass mode
+    #[default]
+    Bypass = 0b000,
+    /// FIFO mode
+    FIFO = 0b001,
+    /// Stream (continuous) mode
+    Stream = 0b010,
+    /// Bypass-to-FIFO mode
+    Bypass_to_FIFO = 0b101,
+    /// Bypass-to-stream mode
+    Bypass_to_stream = 0b110,
+    /// Stream-to-FIFO mode
+    Stream_to_FIFO = 0b111,
+}
+
+impl FIFO_MODE {
+     pub fn value(self) -> u8 {
+        self as u8 // already in the right position, can be used directly
+    }
+
+     pub(crate) fn from_trigger(trigger: FIFO_TRIGGER) -> Self {
+        match trigger {
+            FIFO_TRIGGER::Stream_to_FIFO => FIFO_MODE::Stream_to_FIFO,
+            FIFO_TRIGGER::Bypass_to_stream => FIFO_MODE::Bypass_to_stream,
+            FIFO_TRIGGER::Bypass_to_FIFO => FIFO_MODE::Bypass_to_FIFO,
+        }
+    }
+
+     pub(crate) fn from_value(value: u8) -> Option<Self> {
+        match value & Bitmasks::F_MODE_MASK {
+            0b000 | 0b100 => Some(FIFO_MODE::Bypass),
+            0b001 => Some(FIFO_MODE::FIFO),
+            0b010 | 0b011 => Some(FIFO_MODE::Stream),
+            0b101 => Some(FIFO_MODE::Bypass_to_FIFO),
+            0b110 => Some(FIFO_MODE::Bypass_to_stream),
+            0b111 => Some(FIFO_MODE::Stream_to_FIFO),
+            _ => None,
+        }
+    }
+}
+
+/// Values of CTRL_REG1 and CTRL_REG2 for the settings common to LPS22DF and LPS28DFW
+pub(crate) fn config_registers<C: Variant<ODR = ODR>>(config: &SensorConfig<C>) -> Option<(u8, u8)> {
+    let continuous = !matches!(config.odr, ODR::OneShot);
+    // the low-pass filter works in continuous mode only
+    if config.enable_lowpass.status() && !continuous {
+        return None;
+    }
+    let ctrl_reg1 = config.odr.value() | config.averaging.value();
+    let mut ctrl_reg2 = 0u8;
+    if config.enable_lowpass.status() {
+        ctrl_reg2 |= Bitmasks::EN_LPFP;
+    }
+    if config.lowpass_config.status() {
+        ctrl_reg2 |= Bitmasks::LPFP_CFG;
+    }
+    if config.enable_bdu.status() {
+        ctrl_reg2 |= Bitmasks::BDU;
+    }
+    Some((ctrl_reg1, ctrl_reg2))
+}
+
+/// Decodes the settings common to LPS22DF and LPS28DFW
+// the struct updates fill in the fields of the other models, if enabled
+#[allow(clippy::needless_update)]
+pub(crate) fn decode_config<C>(registers: &ConfigRegisters) -> Option<DeviceConfig<C>>
+where
+    C: Variant<ODR = ODR, FIFO_MODE = FIFO_MODE>,
+{
+    let sensor = SensorConfig {
+        odr: ODR::from_value(registers.ctrl_reg1)?,
+        averaging: AVG::from_value(registers.ctrl_reg1)?,
+        enable_bdu: FLAG::from_status(registers.ctrl_reg2 & Bitmasks::BDU != 0),
+        enable_lowpass: FLAG::from_status(registers.ctrl_reg2 & Bitmasks::EN_LPFP != 0),
+        lowpass_config: FLAG::from_status(registers.ctrl_reg2 & Bitmasks::LPFP_CFG != 0),
+        ..SensorConfig::default()
+    };
+    let fifo_mode = FIFO_MODE::from_value(registers.fifo_ctrl)?;
+    let fifo = FIFOConfig {
+        enable_watermark: FLAG::from_status(registers.fifo_ctrl & Bitmasks::STOP_ON_WTM != 0),
+        fifo_mode,
+        watermark_level: registers.fifo_wtm & Bitmasks::WTM_MASK,
+        ..FIFOConfig::default()
+    };
+    Some(DeviceConfig {
+        sensor,
+        fifo_on: FIFO_ON::from_status(!matches!(fifo_mode, FIFO_MODE::Bypass)),
+        fifo,
+        interrupts: InterruptConfig::from_lps22df_registers(registers.ctrl_reg3, registers.ctrl_reg4, registers.interrupt_cfg),
+    })
+}
+
+/// Values of FIFO_CTRL and FIFO_WTM, common to LPS22DF and LPS28DFW
+pub(crate) fn fifo_registers<C: Variant<FIFO_MODE = FIFO_MODE>>(fifo_on: FIFO_ON, config: &FIFOConfig<C>) -> Option<(u8, u8, u8)> {
+    let mut fifo_ctrl = 0u8;
+    if config.enable_watermark.status() {
+        fifo_ctrl |= Bitmasks::STOP_ON_WTM;
+    }
+    // there is no FIFO enable bit, the FIFO is disabled in Bypass mode
+    if fifo_on.status() {
+        fifo_ctrl |= config.fifo_mode.value();
+    }
+    let fifo_wtm = config.watermark_level & Bitmasks::WTM_MASK;
+    Some((0, fifo_ctrl, fifo_wtm))
+}
+
+#[cfg(feature = "lps22df")]
+impl Sealed for LPS22DF {}
+
+#[cfg(feature = "lps22df")]
+impl Variant for LPS22DF {
+    type ODR = ODR;
+    type FIFO_MODE = FIFO_MODE;
+
+    const WHOAMI: u8 = 0b10110100; // decimal value 180
+    const MULTIBYTE: bool = false;
+    const FIFO_DEPTH: u8 = 128;
+    /// The output of the temperature sensor must be divided by 100, see Table 2 of the datasheet.
+    const TEMP_SCALE: u16 = 100;
+    const TEMP_OFFSET_CENTI: i16 = 0;
+
+    const WHO_AM_I: u8 = Registers::WHO_AM_I as u8;
+    const CTRL_REG1: u8 = Registers::CTRL_REG1 as u8;
+    const CTRL_REG2: u8 = Registers::CTRL_REG2 as u8;
+    const CTRL_REG3: u8 = Registers::CTRL_REG3 as u8;
+    const CTRL_REG4: Option<u8> = Some(Registers::CTRL_REG4 as u8);
+    const INTERRUPT_CFG: u8 = Registers::INTERRUPT_CFG as u8;
+    const FIFO_CTRL: u8 = Registers::FIFO_CTRL as u8;
+    const FIFO_WTM: Option<u8> = Some(Registers::FIFO_WTM as u8);
+    const REF_P: u8 = Registers::REF_P_L as u8;
+    const REF_P_LEN: usize = 2;
+    const RPDS_L: u8 = Registers::RPDS_L as u8;
+    // block data update and low-pass filter are in CTRL_REG2, there is no RES_CONF register
+    const RES_CONF: u8 = Registers::CTRL_REG2 as u8;
+    const RES_CONF_MASK: u8 = Bitmasks::BDU | Bitmasks::EN_LPFP | Bitmasks::LPFP_CFG;
+    const RPDS_H: u8 = Registers::RPDS_H as u8;
+    const THS_P_L: u8 = Registers::THS_P_L as u8;
+    const THS_P_H: u8 = Registers::THS_P_H as u8;
+    const INT_SOURCE: u8 = Registers::INT_SOURCE as u8;
+    const FIFO_STATUS: u8 = Registers::FIFO_STATUS1 as u8;
+    const FIFO_STATUS_LEN: usize = 2;
+    // the FIFO holds pressure only
+    const FIFO_DATA: u8 = Registers::FIFO_DATA_OUT_PRESS_XL as u8;
+    const FIFO_DATA_LEN: usize = 3;
+    const STATUS: u8 = Registers::STATUS as u8;
+    const PRESS_OUT_XL: u8 = Registers::PRESS_OUT_XL as u8;
+    const TEMP_OUT_L: u8 = Registers::TEMP_OUT_L as u8;
+
+    const ODR_MASK: u8 = Bitmasks::ODR_MASK;
+    const PD: u8 = 0;
+    const BDU_REG: u8 = Registers::CTRL_REG2 as u8;
+    const BDU: u8 = Bitmasks::BDU;
+    const BOOT: u8 = Bitmasks::BOOT;
+    // the FIFO is enabled by selecting a mode other than Bypass
+    const FIFO_EN: u8 = 0;
+    const CTRL_REG2_FIFO_MASK: u8 = 0;
+    const I2C_DIS_REG: u8 = Registers::IF_CTRL as u8;
+    const I2C_DIS: u8 = Bitmasks::I2C_I3C_DIS;
+    const SWRESET: u8 = Bitmasks::SWRESET;
+    const ONE_SHOT: u8 = Bitmasks::ONE_SHOT;
+    const AUTOZERO_REG: u8 = Registers::INTERRUPT_CFG as u8;
+    const AUTOZERO: u8 = Bitmasks::AUTOZERO;
+    const RESET_AZ_REG: u8 = Registers::INTERRUPT_CFG as u8;
+    const RESET_AZ: u8 = Bitmasks::RESET_AZ;
+    const DIFF_EN_REG: u8 = Registers::CTRL_REG4 as u8;
+    const DIFF_EN: u8 = Bitmasks::INT_EN;
+    const IA: u8 = Bitmasks::IA;
+    const PL: u8 = Bitmasks::PL;
+    const PH: u8 = Bitmasks::PH;
+    const FTH_FIFO: u8 = Bitmasks::FIFO_WTM_IA;
+    const OVR: u8 = Bitmasks::FIFO_OVR_IA;
+    const T_OR: u8 = Bitmasks::T_OR;
+    const P_OR: u8 = Bitmasks::P_OR;
+    const T_DA: u8 = Bitmasks::T_DA;
+    const P_DA: u8 = Bitmasks::P_DA;
+
+    fn odr_value(odr: ODR) -> u8 {
+        odr.value()
+    }
+
+    fn odr_period_us(odr: ODR) -> Option<u32> {
+        odr.period_us()
+    }
+
+    fn odr_from_register(ctrl_reg1: u8) -> Option<ODR> {
+        ODR::from_value(ctrl_reg1)
+    }
+
+    fn press_sensitivity(_config: &SensorConfig<Self>) -> u16 {
+        crate::PRESS_SENSITIVITY
+    }
+
+    fn config_registers(config: &SensorConfig<Self>) -> Option<(u8, u8)> {
+        config_registers(config)
+    }
+
+    fn decode_config(registers: &ConfigRegisters) -> Option<DeviceConfig<Self>> {
+        decode_config(registers)
+    }
+
+    fn fifo_registers(fifo_on: FIFO_ON, config: &FIFOConfig<Self>) -> Option<(u8, u8, u8)> {
+        fifo_registers(fifo_on, config)
+    }
+
+    fn fifo_level(fifo_status: &[u8]) -> u8 {
+        // FIFO_STATUS1 holds the number of unread samples
+        fifo_status[0]
+    }
+
+    fn trigger_fifo_mode(trigger: FIFO_TRIGGER) -> FIFO_MODE {
+        FIFO_MODE::from_trigger(trigger)
+    }
+}
@@ -0,0 +1,523 @@
+//! LPS25HB register mapping, bitmasks, output data rates and FIFO modes
+
+use super::{Sealed, Variant};
+use crate::config::{ConfigRegisters, DeviceConfig, SensorConfig};
+use crate::fifo::{FIFOConfig, FIFO_TRIGGER};
+use crate::interrupt::InterruptConfig;
+use crate::{FIFO_ON, FLAG};
+
+/// LPS25HB sensor model
+#[derive(Debug, Clone, Copy)]
+pub struct LPS25HB;
+
+/// Registers
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub enum Registers {
+     /// Reference pressure register.
+     REF_P_XL = 0x08,
+     /// Reference pressure register.
+     REF_P_L = 0x09,
+     /// Reference pressure register.
+     REF_P_H = 0x0A,
+     /// Who Am I (identifies the chip).
+     WHO_AM_I = 0x0F,
+     /// Resolution configuration.
+     RES_CONF = 0x10,
+     /// Control register 1.
+     CTRL_REG1 = 0x20,
+     /// Control register 2.
+     CTRL_REG2 = 0x21,
+     /// Control register 3.
+     CTRL_REG3 = 0x22,
+     /// Control register 4.
+     CTRL_REG4 = 0x23,
+     /// Interrupt control.
+     INTERRUPT_CFG = 0x24,
+     /// Interrupt configuration.
+     INT_SOURCE = 0x25,
+     /// Status register.
+     STATUS = 0x27,
+     /// Pressure output register.
+     PRESS_OUT_XL = 0x28,
+     /// Pressure output register.
+     PRESS_OUT_L = 0x29,
+     /// Pressure output register.
+     PRESS_OUT_H = 0x2A,
+     /// Temperature output register.
+     TEMP_OUT_L = 0x2B,
+     /// Temperature output register.
+     TEMP_OUT_H = 0x2C,
+     /// FIFO configuration register.
+     FIFO_CTRL = 0x2E,
+     /// FIFO status register.
+     FIFO_STATUS = 0x2F,
+     /// Pressure threshold low.
+     THS_P_L = 0x30,
+     /// Pressure threshold high.
+     THS_P_H = 0x31,
+     /// Pressure offset register.
+     RPDS_L = 0x39,
+     /// Pressure offset register.
+     RPDS_H = 0x3A,
+}
+
+impl Registers {
+     pub fn addr(self) -> u8 {
+         self as u8
+     }
+}
+
+/// Bit masks
+#[allow(non_camel_case_types)]
+pub struct Bitmasks;
+
+#[allow(dead_code)]
+/// LPS25HB Bit masks
+impl Bitmasks {
+    // === RES_CONF (0x10) ===
+    pub (crate) const AVGT_MASK: u8 = 0b0000_1100;
+    pub (crate) const AVGP_MASK: u8 = 0b0000_0011;
+
+    // === CTRL_REG1 (0x20) ===
+    /// Power down control
+    pub (crate) const PD: u8 = 0b1000_0000;
+    /// Output data rate selection
+    pub (crate) const ODR_MASK: u8 = 0b0111_0000;
+    pub (crate) const DIFF_EN: u8 = 0b0000_1000;
+    pub (crate) const BDU: u8 = 0b0000_0100;
+    pub (crate) const RESET_AZ: u8 = 0b0000_0010;
+    pub (crate) const SIM: u8 = 0b0000_0001;
+
+    // === CTRL_REG2 (0x21) ===
+    pub (crate) const BOOT: u8 = 0b1000_0000;
+    pub (crate) const FIFO_EN: u8 = 0b0100_0000;
+    pub (crate) const STOP_ON_FTH: u8 = 0b0010_0000;
+    pub (crate) const FIFO_MEAN_DEC: u8 = 0b0001_0000;
+    pub (crate) const I2C_DIS: u8 = 0b0000_1000;
+    pub (crate) const SWRESET: u8 = 0b0000_0100;
+    pub (crate) const AUTOZERO: u8 = 0b0000_0010;
+
+    /// Enable single shot to acquire a new dataset
+    pub (crate) const ONE_SHOT: u8 = 0b0000_0001;
+
+    // === CTRL_REG3 (0x22) ===
+    pub (crate) const INT_H_L: u8 = 0b1000_0000;
+    pub (crate) const PP_OD: u8 = 0b0100_0000;
+    pub (crate) const INT_S_MASK: u8 = 0b0000_0011;
+
+    // === CTRL_REG4 (0x23) ===
+    pub (crate) const F_EMPTY: u8 = 0b0000_1000;
+    pub (crate) const F_FTH: u8 = 0b0000_0100;
+    pub (crate) const F_OVR: u8 = 0b0000_0010;
+    pub (crate) const DRDY: u8 = 0b0000_0001;
+
+    // === INTERRUPT_CFG (0x24) ===
+    pub (crate) const LIR: u8 = 0b0000_0100;
+    pub (crate) const PL_E: u8 = 0b0000_0010;
+    pub (crate) const PH_E: u8 = 0b0000_0001;
+
+    // === FIFO_CTRL (0x2E) ===
+    pub (crate) const F_MODE_MASK: u8 = 0b1110_0000;
+    pub (crate) const WTM_POINT_MASK: u8 = 0b0001_1111;
+
+    // === INT_SOURCE (0x25) ===
+    pub (crate) const IA: u8 = 0b0000_0100;
+    pub (crate) const PL: u8 = 0b0000_0010;
+    pub (crate) const PH: u8 = 0b0000_0001;
+
+    // === STATUS_REG (0x27) ===
+    pub (crate) const P_OR: u8 = 0b0010_0000;
+    pub (crate) const T_OR: u8 = 0b0001_0000;
+    pub (crate) const P_DA: u8 = 0b0000_0010;
+    pub (crate) const T_DA: u8 = 0b0000_0001;
+
+    // === FIFO_STATUS (0x2F) ===
+    pub (crate) const FTH_FIFO: u8 = 0b1000_0000;
+    pub (crate) const OVR: u8 = 0b0100_0000;
+    pub (crate) const EMPTY_FIFO: u8 = 0b0010_0000;
+    pub (crate) const FSS_MASK: u8 = 0b0001_1111;
+}
+
+/// Output data rate and power mode selection (ODR). (Refer to Table 20)
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ODR {
+    /// Power-down / One-shot mode enabled
+    #[default]
+    OneShot = 0b000,
+    /// 1 Hz
+    _1Hz = 0b001,
+    /// 7 Hz
+    _7Hz = 0b010,
+    /// 12.5 Hz
+    _12_5Hz = 0b011,
+    /// 25 Hz
+    _25Hz = 0b100,
+}
+
+impl ODR {
+     pub fn value(self) -> u8 {
+        (self as u8) << 4 // shifted into the right position, can be used directly
+     }
+
+     /// Time between two samples in microseconds, `None` in one-shot mode
+     pub fn period_us(self) -> Option<u32> {
+        match self {
+            ODR::OneShot => None,
+            ODR::_1Hz => Some(1_000_000),
+            ODR::_7Hz => Some(142_857),
+            ODR::_12_5Hz => Some(80_000),
+            ODR::_25Hz => Some(40_000),
+        }
+     }
+
+     pub(crate) fn from_value(value: u8) -> Option<Self> {
+        match (value & Bitmasks::ODR_MASK) >> 4 {
+            0b000 => Some(ODR::OneShot),
+            0b001 => Some(ODR::_1Hz),
+            0b010 => Some(ODR::_7Hz),
+            0b011 => Some(ODR::_12_5Hz),
+            0b100 => Some(ODR::_25Hz),
+            _ => None,
+        }
+     }
+}
+
+/// FIFO mode selection. (Refer to Table 22)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FIFO_MODE {
+    /// Bypass mode
+    #[default]
+    Bypass = 0b000,
+    /// FIFO mode
+    FIFO = 0b001,
+    /// Stream mode
+    Stream = 0b010,
+    /// Stream-to-FIFO mode
+    Stream_to_FIFO = 0b011,
+    /// Bypass-to-stream mode
+    Bypass_to_stream = 0b100,
+    /// FIFO Mean mode
+    FIFO_Mean = 0b110,
+    /// Bypass-to-FIFO mode
+    Bypass_to_FIFO = 0b111,
+}
+
+impl FIFO_MODE {
+     pub fn value(self) -> u8 {
+        (self as u8) << 5 // shifted into the right position, can be used directly
+    }
+
+     pub(crate) fn from_trigger(trigger: FIFO_TRIGGER) -> Self {
+        match trigger {
+            FIFO_TRIGGER::Stream_to_FIFO => FIFO_MODE::Stream_to_FIFO,
+            FIFO_TRIGGER::Bypass_to_stream => FIFO_MODE::Bypass_to_stream,
+            FIFO_TRIGGER::Bypass_to_FIFO => FIFO_MODE::Bypass_to_FIFO,
+        }
+    }
+
+     pub(crate) fn from_value(value: u8) -> Option<Self> {
+        match (value & Bitmasks::F_MODE_MASK) >> 5 {
+            0b000 => Some(FIFO_MODE::Bypass),
+            0b001 => Some(FIFO_MODE::FIFO),
+            0b010 => Some(FIFO_MODE::Stream),
+            0b011 => Some(FIFO_MODE::Stream_to_FIFO),
+            0b100 => Some(FIFO_MODE::Bypass_to_stream),
+            0b110 => Some(FIFO_MODE::FIFO_Mean),
+            0b111 => Some(FIFO_MODE::Bypass_to_FIFO),
+            _ => None,
+        }
+    }
+}
+
+/// FIFO Mean mode running average sample size. (Refer to Table 23)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+pub enum FIFO_MEAN {
+    /// 2-sample moving average
+    _2sample = 0b00001,
+    /// 4-sample moving average
+    _4sample = 0b00011,
+    /// 8-sample moving average
+    _8sample = 0b00111,
+    /// 16-sample moving average
+    _16sample = 0b01111,
+    /// 32-sample moving average
+    _32sample = 0b11111,
+}
+
+impl FIFO_MEAN {
+    pub fn value(self) -> u8 {
+        self as u8 // no need to shift, bits 0:4
+     }
+
+    pub(crate) fn from_value(value: u8) -> Option<Self> {
+        match value & Bitmasks::WTM_POINT_MASK {
+            0b00001 => Some(FIFO_MEAN::_2sample),
+            0b00011 => Some(FIFO_MEAN::_4sample),
+            0b00111 => Some(FIFO_MEAN::_8sample),
+            0b01111 => Some(FIFO_MEAN::_16sample),
+            0b11111 => Some(FIFO_MEAN::_32sample),
+            _ => None,
+        }
+    }
+
+    /// Output data rate of the high-resolution mode (see `high_resolution_mode()`): the highest one
+    /// at which the moving average still covers the 1 Hz output period, so that the decimation discards no sample.
+    /// N samples at the output data rate f span N / f seconds, so f must not exceed N Hz:
+    /// among 1, 7, 12.5 and 25 Hz, 1 Hz for 2 and 4 samples, 7 Hz for 8, 12.5 Hz for 16 and 25 Hz for 32.
+    pub fn high_resolution_odr(self) -> ODR {
+        match self {
+            FIFO_MEAN::_2sample | FIFO_MEAN::_4sample => ODR::_1Hz,
+            FIFO_MEAN::_8sample => ODR::_7Hz,
+            FIFO_MEAN::_16sample => ODR::_12_5Hz,
+            FIFO_MEAN::_32sample => ODR::_25Hz,
+        }
+    }
+}
+
+/// Internal averages of the high-resolution mode: AVGT 16 and AVGP 32,
+/// the recommended setting for FIFO Mean mode (AVGP 512 can't be used at 25 Hz)
+pub(crate) const HIGH_RESOLUTION_RES_CONF: u8 = 0b0000_0101;
+
+/// Temperature resolution configuration, number of internal average(Refer to Table 18)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+pub enum TEMP_RES {
+    /// Nr. internal average 8
+    _8 = 0b00,
+    /// Nr. internal average 16
+    _16 = 0b01,
+    /// Nr. internal average 32
+    _32 = 0b10,
+    /// Nr. internal average 64
+    _64 = 0b11,
+}
+
+impl TEMP_RES {
+    pub fn value(self) -> u8 {
+        (self as u8) << 2 // shifted into the right position, can be used directly
+    }
+
+    pub(crate) fn from_value(value: u8) -> Self {
+        match (value & Bitmasks::AVGT_MASK) >> 2 {
+            0b00 => TEMP_RES::_8,
+            0b01 => TEMP_RES::_16,
+            0b10 => TEMP_RES::_32,
+            _ => TEMP_RES::_64,
+        }
+    }
+}
+
+/// Pressure resolution configuration, number of internal average(Refer to Table 19)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+pub enum PRESS_RES {
+    /// Nr. internal average 8
+    _8 = 0b00,
+    /// Nr. internal average 32
+    _32 = 0b01,
+    /// Nr. internal average 128
+    _128 = 0b10,
+    /// Nr. internal average 512
+    _512 = 0b11,
+}
+
+impl PRESS_RES {
+    pub fn value(self) -> u8 {
+        self as u8 // no need to shift
+    }
+
+    pub(crate) fn from_value(value: u8) -> Self {
+        match value & Bitmasks::AVGP_MASK {
+            0b00 => PRESS_RES::_8,
+            0b01 => PRESS_RES::_32,
+            0b10 => PRESS_RES::_128,
+            _ => PRESS_RES::_512,
+        }
+    }
+}
+
+impl Sealed for LPS25HB {}
+
+impl Variant for LPS25HB {
+    type ODR = ODR;
+    type FIFO_MODE = FIFO_MODE;
+
+    const WHOAMI: u8 = 0b10111101; // decimal value 189
+    const MULTIBYTE: bool = true;
+    const FIFO_DEPTH: u8 = 32;
+    /// The output of the temperature sensor must be divided by 480, see Table 3 of the datasheet.
+    const TEMP_SCALE: u16 = 480;
+    /// An offset value must be added to the result. This is NOT mentioned in the LPS25HB datasheet, but is described in the LPS25H datasheet.
+    // https://www.st.com/resource/en/technical_note/dm00242307-how-to-interpret-pressure-and-temperature-readings-in-the-lps25hb-pressure-sensor-stmicroelectronics.pdf
+    const TEMP_OFFSET_CENTI: i16 = 4250; // 42.5 °C
+
+    const WHO_AM_I: u8 = Registers::WHO_AM_I as u8;
+    const CTRL_REG1: u8 = Registers::CTRL_REG1 as u8;
+    const CTRL_REG2: u8 = Registers::CTRL_REG2 as u8;
+    const CTRL_REG3: u8 = Registers::CTRL_REG3 as u8;
+    const CTRL_REG4: Option<u8> = Some(Registers::CTRL_REG4 as u8);
+    const INTERRUPT_CFG: u8 = Registers::INTERRUPT_CFG as u8;
+    const FIFO_CTRL: u8 = Registers::FIFO_CTRL as u8;
+    const FIFO_WTM: Option<u8> = None;
+    const REF_P: u8 = Registers::REF_P_XL as u8;
+    const REF_P_LEN: usize = 3;
+    const RPDS_L: u8 = Registers::RPDS_L as u8;
+    const RES_CONF: u8 = Registers::RES_CONF as u8;
+    const RES_CONF_MASK: u8 = 0xFF;
+    const RPDS_H: u8 = Registers::RPDS_H as u8;
+    const THS_P_L: u8 = Registers::THS_P_L as u8;
+    const THS_P_H: u8 = Registers::THS_P_H as u8;
+    const INT_SOURCE: u8 = Registers::INT_SOURCE as u8;
+    const FIFO_STATUS: u8 = Registers::FIFO_STATUS as u8;
+    const FIFO_STATUS_LEN: usize = 1;
+    // the FIFO holds pressure only, read from the output registers
+    const FIFO_DATA: u8 = Registers::PRESS_OUT_XL as u8;
+    const FIFO_DATA_LEN: usize = 3;
+    const STATUS: u8 = Registers::STATUS as u8;
+    const PRESS_OUT_XL: u8 = Registers::PRESS_OUT_XL as u8;
+    const TEMP_OUT_L: u8 = Registers::TEMP_OUT_L as u8;
+
+    const ODR_MASK: u8 = Bitmasks::ODR_MASK;
+    const PD: u8 = Bitmasks::PD;
+    const BDU_REG: u8 = Registers::CTRL_REG1 as u8;
+    const BDU: u8 = Bitmasks::BDU;
+    const BOOT: u8 = Bitmasks::BOOT;
+    const FIFO_EN: u8 = Bitmasks::FIFO_EN;
+    const CTRL_REG2_FIFO_MASK: u8 = Bitmasks::FIFO_EN | Bitmasks::STOP_ON_FTH | Bitmasks::FIFO_MEAN_DEC;
+    const I2C_DIS_REG: u8 = Registers::CTRL_REG2 as u8;
+    const I2C_DIS: u8 = Bitmasks::I2C_DIS;
+    const SWRESET: u8 = Bitmasks::SWRESET;
+    const ONE_SHOT: u8 = Bitmasks::ONE_SHOT;
+    const AUTOZERO_REG: u8 = Registers::CTRL_REG2 as u8;
+    const AUTOZERO: u8 = Bitmasks::AUTOZERO;
+    const RESET_AZ_REG: u8 = Registers::CTRL_REG1 as u8;
+    const RESET_AZ: u8 = Bitmasks::RESET_AZ;
+    const DIFF_EN_REG: u8 = Registers::CTRL_REG1 as u8;
+    const DIFF_EN: u8 = Bitmasks::DIFF_EN;
+    const IA: u8 = Bitmasks::IA;
+    const PL: u8 = Bitmasks::PL;
+    const PH: u8 = Bitmasks::PH;
+    const FTH_FIFO: u8 = Bitmasks::FTH_FIFO;
+    const OVR: u8 = Bitmasks::OVR;
+    const T_OR: u8 = Bitmasks::T_OR;
+    const P_OR: u8 = Bitmasks::P_OR;
+    const T_DA: u8 = Bitmasks::T_DA;
+    const P_DA: u8 = Bitmasks::P_DA;
+
+    fn odr_value(odr: ODR) -> u8 {
+        odr.value()
+    }
+
+    fn odr_period_us(odr: ODR) -> Option<u32> {
+        odr.period_us()
+    }
+
+    fn odr_from_register(ctrl_reg1: u8) -> Option<ODR> {
+        ODR::from_value(ctrl_reg1)
+    }
+
+    fn press_sensitivity(_config: &SensorConfig<Self>) -> u16 {
+        crate::PRESS_SENSITIVITY
+    }
+
+    fn config_registers(config: &SensorConfig<Self>) -> Option<(u8, u8)> {
+        let continuous = !matches!(config.odr, ODR::OneShot);
+        // the output data rate has no effect while the sensor is in power down
+        if continuous && !config.power_on.status() {
+            return None;
+        }
+        let mut ctrl_reg1 = config.odr.value();
+        if config.power_on.status() {
+            ctrl_reg1 |= Bitmasks::PD;
+        }
+        if config.enable_bdu.status() {
+            ctrl_reg1 |= Bitmasks::BDU;
+        }
+        let mut res_conf = 0u8;
+        res_conf |= config.temperature_resolution.value();
+        res_conf |= config.pressure_resolution.value();
+        Some((ctrl_reg1, res_conf))
+    }
+
+    // the struct updates fill in the fields of the other models, if enabled
+    #[allow(clippy::needless_update)]
+    fn decode_config(registers: &ConfigRegisters) -> Option<DeviceConfig<Self>> {
+        let sensor = SensorConfig {
+            odr: ODR::from_value(registers.ctrl_reg1)?,
+            enable_bdu: FLAG::from_status(registers.ctrl_reg1 & Bitmasks::BDU != 0),
+            power_on: FLAG::from_status(registers.ctrl_reg1 & Bitmasks::PD != 0),
+            temperature_resolution: TEMP_RES::from_value(registers.res_conf),
+            pressure_resolution: PRESS_RES::from_value(registers.res_conf),
+            ..SensorConfig::default()
+        };
+        let fifo_mode = FIFO_MODE::from_value(registers.fifo_ctrl)?;
+        let fifo = match fifo_mode {
+            // in FIFO Mean mode the WTM_POINT bits select the number of samples
+            FIFO_MODE::FIFO_Mean => FIFOConfig {
+                fifo_mode,
+                fifo_mean_config: FIFO_MEAN::from_value(registers.fifo_ctrl)?,
+                ..FIFOConfig::default()
+            },
+            _ => FIFOConfig {
+                fifo_mode,
+                watermark_level: registers.fifo_ctrl & Bitmasks::WTM_POINT_MASK,
+                ..FIFOConfig::default()
+            },
+        };
+        let fifo = FIFOConfig {
+            enable_watermark: FLAG::from_status(registers.ctrl_reg2 & Bitmasks::STOP_ON_FTH != 0),
+            enable_decimating: FLAG::from_status(registers.ctrl_reg2 & Bitmasks::FIFO_MEAN_DEC != 0),
+            ..fifo
+        };
+        Some(DeviceConfig {
+            sensor,
+            fifo_on: FIFO_ON::from_status(registers.ctrl_reg2 & Bitmasks::FIFO_EN != 0),
+            fifo,
+            interrupts: InterruptConfig::from_lps25hb_registers(
+                registers.ctrl_reg1,
+                registers.ctrl_reg3,
+                registers.ctrl_reg4,
+                registers.interrupt_cfg,
+            ),
+        })
+    }
+
+    fn fifo_registers(_fifo_on: FIFO_ON, config: &FIFOConfig<Self>) -> Option<(u8, u8, u8)> {
+        let fifo_mean = matches!(config.fifo_mode, FIFO_MODE::FIFO_Mean);
+        // decimation to 1 Hz is available in FIFO Mean mode only
+        if config.enable_decimating.status() && !fifo_mean {
+            return None;
+        }
+        let mut ctrl_reg2 = 0u8;
+        if config.enable_watermark.status() {
+            ctrl_reg2 |= Bitmasks::STOP_ON_FTH;
+        }
+        if config.enable_decimating.status() {
+            ctrl_reg2 |= Bitmasks::FIFO_MEAN_DEC;
+        }
+        let mut fifo_ctrl = 0u8;
+        fifo_ctrl |= config.fifo_mode.value();
+        let wtm = match fifo_mean {
+            true => config.fifo_mean_config.value(),
+            false => config.watermark_level,
+        };
+        fifo_ctrl |= wtm & Bitmasks::WTM_POINT_MASK;
+        Some((ctrl_reg2, fifo_ctrl, 0))
+    }
+
+    fn fifo_level(fifo_status: &[u8]) -> u8 {
+        // FSS is the number of unread samples minus one, the FIFO is empty if EMPTY_FIFO is set
+        match fifo_status[0] & Bitmasks::EMPTY_FIFO {
+            0 => (fifo_status[0] & Bitmasks::FSS_MASK) + 1,
+            _ => 0,
+        }
+    }
+
+    fn trigger_fifo_mode(trigger: FIFO_TRIGGER) -> FIFO_MODE {
+        FIFO_MODE::from_trigger(trigger)
+    }
+}
@@ -0,0 +1,161 @@
+//! LPS28DFW full-scale modes; the register map, bitmasks, output data rates, averaging
+//! and FIFO modes are the same as on LPS22DF, see the `lps22df` module.
+//!
+//! The LPS28DFW has an I2C/I3C interface only.
+
+use super::{Sealed, Variant};
+use crate::config::{ConfigRegisters, DeviceConfig, SensorConfig};
+use crate::fifo::{FIFOConfig, FIFO_TRIGGER};
+use crate::FIFO_ON;
+
+pub use super::lps22df::{Bitmasks, Registers, AVG, FIFO_MODE, ODR};
+
+/// LPS28DFW sensor model
+#[derive(Debug, Clone, Copy)]
+pub struct LPS28DFW;
+
+/// Full-scale mode selection (FS_MODE), selects the pressure sensitivity. (Refer to Table 2)
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FS_MODE {
+    /// Mode 1: 260 to 1260 hPa, 4096 LSB/hPa
+    #[default]
+    _1260hPa,
+    /// Mode 2: 260 to 4060 hPa, 2048 LSB/hPa
+    _4060hPa,
+}
+
+impl FS_MODE {
+    /// Value of the FS_MODE bit of CTRL_REG2, shifted into the right position
+    pub fn value(self) -> u8 {
+        match self {
+            FS_MODE::_1260hPa => 0,
+            FS_MODE::_4060hPa => Bitmasks::FS_MODE,
+        }
+    }
+
+    /// Pressure sensitivity in LSB/hPa
+    pub fn sensitivity(self) -> u16 {
+        match self {
+            FS_MODE::_1260hPa => 4096,
+            FS_MODE::_4060hPa => 2048,
+        }
+    }
+
+    pub(crate) fn from_value(value: u8) -> Self {
+        match value & Bitmasks::FS_MODE {
+            0 => FS_MODE::_1260hPa,
+            _ => FS_MODE::_4060hPa,
+        }
+    }
+}
+
+impl Sealed for LPS28DFW {}
+
+impl Variant for LPS28DFW {
+    type ODR = ODR;
+    type FIFO_MODE = FIFO_MODE;
+
+    const WHOAMI: u8 = 0b10110100; // decimal value 180, same as LPS22DF
+    const MULTIBYTE: bool = false;
+    const FIFO_DEPTH: u8 = 128;
+    /// The output of the temperature sensor must be divided by 100, see Table 2 of the datasheet.
+    const TEMP_SCALE: u16 = 100;
+    const TEMP_OFFSET_CENTI: i16 = 0;
+
+    const WHO_AM_I: u8 = Registers::WHO_AM_I as u8;
+    const CTRL_REG1: u8 = Registers::CTRL_REG1 as u8;
+    const CTRL_REG2: u8 = Registers::CTRL_REG2 as u8;
+    const CTRL_REG3: u8 = Registers::CTRL_REG3 as u8;
+    const CTRL_REG4: Option<u8> = Some(Registers::CTRL_REG4 as u8);
+    const INTERRUPT_CFG: u8 = Registers::INTERRUPT_CFG as u8;
+    const FIFO_CTRL: u8 = Registers::FIFO_CTRL as u8;
+    const FIFO_WTM: Option<u8> = Some(Registers::FIFO_WTM as u8);
+    const REF_P: u8 = Registers::REF_P_L as u8;
+    const REF_P_LEN: usize = 2;
+    const RPDS_L: u8 = Registers::RPDS_L as u8;
+    // block data update, low-pass filter and full-scale mode are in CTRL_REG2, there is no RES_CONF register
+    const RES_CONF: u8 = Registers::CTRL_REG2 as u8;
+    const RES_CONF_MASK: u8 = Bitmasks::FS_MODE | Bitmasks::BDU | Bitmasks::EN_LPFP | Bitmasks::LPFP_CFG;
+    const RPDS_H: u8 = Registers::RPDS_H as u8;
+    const THS_P_L: u8 = Registers::THS_P_L as u8;
+    const THS_P_H: u8 = Registers::THS_P_H as u8;
+    const INT_SOURCE: u8 = Registers::INT_SOURCE as u8;
+    const FIFO_STATUS: u8 = Registers::FIFO_STATUS1 as u8;
+    const FIFO_STATUS_LEN: usize = 2;
+    // the FIFO holds pressure only
+    const FIFO_DATA: u8 = Registers::FIFO_DATA_OUT_PRESS_XL as u8;
+    const FIFO_DATA_LEN: usize = 3;
+    const STATUS: u8 = Registers::STATUS as u8;
+    const PRESS_OUT_XL: u8 = Registers::PRESS_OUT_XL as u8;
+    const TEMP_OUT_L: u8 = Registers::TEMP_OUT_L as u8;
+
+    const ODR_MASK: u8 = Bitmasks::ODR_MASK;
+    const PD: u8 = 0;
+    const BDU_REG: u8 = Registers::CTRL_REG2 as u8;
+    const BDU: u8 = Bitmasks::BDU;
+    const BOOT: u8 = Bitmasks::BOOT;
+    // the FIFO is enabled by selecting a mode other than Bypass
+    const FIFO_EN: u8 = 0;
+    const CTRL_REG2_FIFO_MASK: u8 = 0;
+    // I2C/I3C is the only interface, it can't be disabled
+    const I2C_DIS_REG: u8 = Registers::IF_CTRL as u8;
+    const I2C_DIS: u8 = 0;
+    const SWRESET: u8 = Bitmasks::SWRESET;
+    const ONE_SHOT: u8 = Bitmasks::ONE_SHOT;
+    const AUTOZERO_REG: u8 = Registers::INTERRUPT_CFG as u8;
+    const AUTOZERO: u8 = Bitmasks::AUTOZERO;
+    const RESET_AZ_REG: u8 = Registers::INTERRUPT_CFG as u8;
+    const RESET_AZ: u8 = Bitmasks::RESET_AZ;
+    const DIFF_EN_REG: u8 = Registers::CTRL_REG4 as u8;
+    const DIFF_EN: u8 = Bitmasks::INT_EN;
+    const IA: u8 = Bitmasks::IA;
+    const PL: u8 = Bitmasks::PL;
+    const PH: u8 = Bitmasks::PH;
+    const FTH_FIFO: u8 = Bitmasks::FIFO_WTM_IA;
+    const OVR: u8 = Bitmasks::FIFO_OVR_IA;
+    const T_OR: u8 = Bitmasks::T_OR;
+    const P_OR: u8 = Bitmasks::P_OR;
+    const T_DA: u8 = Bitmasks::T_DA;
+    const P_DA: u8 = Bitmasks::P_DA;
+
+    fn odr_value(odr: ODR) -> u8 {
+        odr.value()
+    }
+
+    fn odr_period_us(odr: ODR) -> Option<u32> {
+        odr.period_us()
+    }
+
+    fn odr_from_register(ctrl_reg1: u8) -> Option<ODR> {
+        ODR::from_value(ctrl_reg1)
+    }
+
+    fn press_sensitivity(config: &SensorConfig<Self>) -> u16 {
+        config.full_scale.sensitivity()
+    }
+
+    fn config_registers(config: &SensorConfig<Self>) -> Option<(u8, u8)> {
+        let (ctrl_reg1, ctrl_reg2) = super::lps22df::config_registers(config)?;
+        Some((ctrl_reg1, ctrl_reg2 | config.full_scale.value()))
+    }
+
+    fn decode_config(registers: &ConfigRegisters) -> Option<DeviceConfig<Self>> {
+        let mut config = super::lps22df::decode_config::<Self>(registers)?;
+        config.sensor.full_scale = FS_MODE::from_value(registers.ctrl_reg2);
+        Some(config)
+    }
+
+    fn fifo_registers(fifo_on: FIFO_ON, config: &FIFOConfig<Self>) -> Option<(u8, u8, u8)> {
+        super::lps22df::fifo_registers(fifo_on, config)
+    }
+
+    fn fifo_level(fifo_status: &[u8]) -> u8 {
+        // FIFO_STATUS1 holds the number of unread samples
+        fifo_status[0]
+    }
+
+    fn trigger_fifo_mode(trigger: FIFO_TRIGGER) -> FIFO_MODE {
+        FIFO_MODE::from_trigger(trigger)
+    }
+}
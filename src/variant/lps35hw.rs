@@ -0,0 +1,17 @@
+//! LPS35HW sensor model, water-resistant sibling of the LPS22HB
+//!
+//! The register map, bitmasks, output data rates, FIFO modes and WHO_AM_I value are the same
+//! as on LPS22HB, see the `lps22hb` module: the "Register mapping" and "Register description"
+//! sections of the [LPS35HW datasheet](https://www.st.com/resource/en/datasheet/lps35hw.pdf)
+//! list the same addresses, bits and reset values as the LPS22HB datasheet. The models differ
+//! by their package (water-resistant) and operating range only.
+//!
+//! The sensor can't be told apart from a LPS22HB: `Device::detect()` returns `Error::AmbiguousDevice`
+//! if another model with the same ID is enabled, use `Device::detect_as(interface, LPS35HW)`
+//! or `LPS2X::new(interface, LPS35HW)` to select it.
+
+pub use super::lps22hb::{Bitmasks, Registers, FIFO_MODE, ODR};
+
+/// LPS35HW sensor model
+#[derive(Debug, Clone, Copy)]
+pub struct LPS35HW;
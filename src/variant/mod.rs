@@ -0,0 +1,203 @@
+//! Supported sensor models
+//!
+//! Each model has its own register map, bitmasks, output data rate and FIFO mode tables.
+//! The driver is generic over the model, so that all the models enabled with features
+//! can be used in the same build.
+
+#[cfg(any(feature = "lps22hb", feature = "lps33hw", feature = "lps35hw"))]
+pub mod lps22hb;
+#[cfg(feature = "lps22hb")]
+pub use self::lps22hb::LPS22HB;
+
+#[cfg(any(feature = "lps22df", feature = "lps28dfw"))]
+pub mod lps22df;
+#[cfg(feature = "lps22df")]
+pub use self::lps22df::LPS22DF;
+
+#[cfg(feature = "lps22hh")]
+pub mod lps22hh;
+#[cfg(feature = "lps22hh")]
+pub use self::lps22hh::LPS22HH;
+
+#[cfg(feature = "lps25hb")]
+pub mod lps25hb;
+#[cfg(feature = "lps25hb")]
+pub use self::lps25hb::LPS25HB;
+
+#[cfg(feature = "lps28dfw")]
+pub mod lps28dfw;
+#[cfg(feature = "lps28dfw")]
+pub use self::lps28dfw::LPS28DFW;
+
+#[cfg(feature = "lps33hw")]
+pub mod lps33hw;
+#[cfg(feature = "lps33hw")]
+pub use self::lps33hw::LPS33HW;
+
+#[cfg(feature = "lps35hw")]
+pub mod lps35hw;
+#[cfg(feature = "lps35hw")]
+pub use self::lps35hw::LPS35HW;
+
+use crate::config::{ConfigRegisters, DeviceConfig, SensorConfig};
+use crate::fifo::{FIFOConfig, FIFO_TRIGGER};
+use crate::FIFO_ON;
+
+mod private {
+    pub trait Sealed {}
+}
+
+pub(crate) use self::private::Sealed;
+
+/// Register addresses, bitmasks and conversion constants of a sensor model.
+///
+/// This trait is sealed: it is implemented by the sensor types of this module only.
+/// Register addresses and bitmasks listed here are the ones used by the functions
+/// common to all the models, model-specific functions use the register map of the model directly.
+#[allow(non_camel_case_types)]
+pub trait Variant: Sealed {
+    /// Output data rate selection
+    type ODR: Copy + core::fmt::Debug + Default;
+    /// FIFO mode selection
+    type FIFO_MODE: Copy + core::fmt::Debug + Default;
+
+    /// Content of the WHO_AM_I register
+    const WHOAMI: u8;
+    /// Multiple byte reads must request address incrementing explicitly (e.g. LPS25HB).
+    /// If not set, address incrementing is controlled by the IF_ADD_INC bit (e.g. LPS22HB).
+    const MULTIBYTE: bool;
+    /// Number of FIFO slots
+    const FIFO_DEPTH: u8;
+    /// The output of the temperature sensor must be divided by this value (LSB/°C)
+    const TEMP_SCALE: u16;
+    /// This value must be added to the scaled output of the temperature sensor, in hundredths of a degree
+    const TEMP_OFFSET_CENTI: i16;
+
+    // === registers ===
+    const WHO_AM_I: u8;
+    const CTRL_REG1: u8;
+    const CTRL_REG2: u8;
+    const CTRL_REG3: u8;
+    /// Control register 4, if the model has it
+    const CTRL_REG4: Option<u8>;
+    const INTERRUPT_CFG: u8;
+    const FIFO_CTRL: u8;
+    /// FIFO watermark register, if the watermark level is not in FIFO_CTRL
+    const FIFO_WTM: Option<u8>;
+    /// First reference pressure register (REF_P_XL, or REF_P_L if REF_P has 16 bits only)
+    const REF_P: u8;
+    /// Number of reference pressure registers (3, or 2 if REF_P has 16 bits only)
+    const REF_P_LEN: usize;
+    const RPDS_L: u8;
+    /// Register holding the resolution / current mode settings of `SensorConfig`
+    /// (RES_CONF, or CTRL_REG2 if there is no RES_CONF register)
+    const RES_CONF: u8;
+    /// Bits of the RES_CONF register set by `init()`
+    const RES_CONF_MASK: u8;
+    const RPDS_H: u8;
+    const THS_P_L: u8;
+    const THS_P_H: u8;
+    const INT_SOURCE: u8;
+    /// First FIFO status register
+    const FIFO_STATUS: u8;
+    /// Number of FIFO status registers, the flags are in the last one
+    const FIFO_STATUS_LEN: usize;
+    const STATUS: u8;
+    const PRESS_OUT_XL: u8;
+    const TEMP_OUT_L: u8;
+    /// First FIFO output register, reading the sample pops it from the FIFO
+    const FIFO_DATA: u8;
+    /// Number of FIFO output registers per sample: 5 with pressure and temperature, 3 with pressure only
+    const FIFO_DATA_LEN: usize;
+
+    // === bitmasks ===
+    /// Output data rate selection (CTRL_REG1)
+    const ODR_MASK: u8;
+    /// Power down control (CTRL_REG1), 0 if the model is in power-down whenever the output data rate is 0
+    const PD: u8;
+    /// Register holding the BDU bit
+    const BDU_REG: u8;
+    /// Block data update
+    const BDU: u8;
+    /// Reboot memory content (CTRL_REG2)
+    const BOOT: u8;
+    /// FIFO enable (CTRL_REG2)
+    const FIFO_EN: u8;
+    /// Bits of CTRL_REG2 set by `configure_fifo()`, FIFO_EN included (0 if the FIFO settings are all in FIFO_CTRL)
+    const CTRL_REG2_FIFO_MASK: u8;
+    /// Register holding the I2C_DIS bit
+    const I2C_DIS_REG: u8;
+    /// Disable I2C interface
+    const I2C_DIS: u8;
+    /// Software reset (CTRL_REG2)
+    const SWRESET: u8;
+    /// Enable single shot to acquire a new dataset (CTRL_REG2)
+    const ONE_SHOT: u8;
+    /// Register holding the AUTOZERO bit
+    const AUTOZERO_REG: u8;
+    /// Autozero enable
+    const AUTOZERO: u8;
+    /// Register holding the RESET_AZ bit
+    const RESET_AZ_REG: u8;
+    /// Autozero reset
+    const RESET_AZ: u8;
+    /// Register holding the bit enabling the pressure interrupts
+    const DIFF_EN_REG: u8;
+    /// Pressure interrupts enable (DIFF_EN, INT_EN on LPS22DF and LPS28DFW)
+    const DIFF_EN: u8;
+    /// Interrupt active (INT_SOURCE)
+    const IA: u8;
+    /// Differential pressure low (INT_SOURCE)
+    const PL: u8;
+    /// Differential pressure high (INT_SOURCE)
+    const PH: u8;
+    /// FIFO watermark status (last FIFO_STATUS register)
+    const FTH_FIFO: u8;
+    /// FIFO overrun status (last FIFO_STATUS register)
+    const OVR: u8;
+    /// Temperature data overrun (STATUS)
+    const T_OR: u8;
+    /// Pressure data overrun (STATUS)
+    const P_OR: u8;
+    /// Temperature data available (STATUS)
+    const T_DA: u8;
+    /// Pressure data available (STATUS)
+    const P_DA: u8;
+
+    /// Value of the ODR bits, shifted into the right position
+    fn odr_value(odr: Self::ODR) -> u8;
+
+    /// Time between two samples in microseconds at the given output data rate, `None` in one-shot mode
+    fn odr_period_us(odr: Self::ODR) -> Option<u32>;
+
+    /// Output data rate decoded from the content of CTRL_REG1, `None` if the ODR bits hold a reserved value
+    fn odr_from_register(ctrl_reg1: u8) -> Option<Self::ODR>;
+
+    /// Pressure sensitivity in LSB/hPa for the given sensor configuration (e.g. full-scale mode)
+    fn press_sensitivity(config: &SensorConfig<Self>) -> u16
+    where
+        Self: Sized;
+
+    /// Values to be written to CTRL_REG1 and RES_CONF for the given sensor configuration,
+    /// `None` if the settings can't be used together
+    fn config_registers(config: &SensorConfig<Self>) -> Option<(u8, u8)>
+    where
+        Self: Sized;
+
+    /// Decodes the configuration registers, `None` if a register holds a reserved value
+    fn decode_config(registers: &ConfigRegisters) -> Option<DeviceConfig<Self>>
+    where
+        Self: Sized;
+
+    /// Values of the `CTRL_REG2_FIFO_MASK` bits of CTRL_REG2 (FIFO_EN excluded) and values written to FIFO_CTRL
+    /// and FIFO_WTM (if the model has it) for the given FIFO configuration, `None` if the settings can't be used together
+    fn fifo_registers(fifo_on: FIFO_ON, config: &FIFOConfig<Self>) -> Option<(u8, u8, u8)>
+    where
+        Self: Sized;
+
+    /// Number of samples stored in the FIFO, decoded from the content of the FIFO status registers
+    fn fifo_level(fifo_status: &[u8]) -> u8;
+
+    /// FIFO mode switching on the interrupt event
+    fn trigger_fifo_mode(trigger: FIFO_TRIGGER) -> Self::FIFO_MODE;
+}